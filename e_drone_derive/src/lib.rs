@@ -0,0 +1,424 @@
+/*
+    #[derive(Protocol)]
+
+    protocol 구조체의 size(), parse(), Serializable::to_vec()를 생성.
+    필드는 선언한 순서대로 little endian으로 읽고 씀.
+
+    필드 타입
+        u8, i8, u16, i16, u32, i32, u64, i64, f32, f64, bool
+        [T; N]                          고정 길이 배열
+        그 외 타입                      size(), parse(), Serializable을 가진 구조체
+
+    필드 속성
+        #[protocol(enum)]               u8로 변환되는 열거형 (IntoPrimitive, TryFromPrimitive)
+        #[protocol(array = N)]          길이가 N인 Vec<T>
+                                        to_vec()은 앞의 N개만 쓰고, 부족한 원소는 0으로 채움
+        #[protocol(tail)]               남은 데이터 전체 (마지막 필드, Vec<T> 또는 String)
+                                        tail이 있으면 size()는 tail을 제외한 최소 길이
+                                        Vec<T>의 T는 size()가 0이 아니어야 함 (0이면 컴파일 오류)
+ */
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, Field, Fields, GenericArgument, Ident, LitStr, PathArguments, Type};
+
+
+#[proc_macro_derive(Protocol, attributes(protocol))]
+pub fn derive_protocol(input: TokenStream) -> TokenStream
+{
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(token_stream) => token_stream.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+
+// -- Kind ----------------------------------------------------------------------------------------------
+// 값 하나를 읽고 쓰는 방법
+enum Kind {
+    Primitive(Ident),
+    Bool,
+    Enum(Type),
+    Nested(Type),
+}
+
+
+impl Kind {
+    fn from_type(ty: &Type, is_enum: bool) -> Kind
+    {
+        if is_enum {
+            return Kind::Enum(ty.clone());
+        }
+
+        if let Type::Path(type_path) = ty {
+            if let Some(ident) = type_path.path.get_ident() {
+                match ident.to_string().as_str() {
+                    "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64" | "f32" | "f64" => return Kind::Primitive(ident.clone()),
+                    "bool" => return Kind::Bool,
+                    _ => {},
+                }
+            }
+        }
+
+        Kind::Nested(ty.clone())
+    }
+
+
+    fn size(&self) -> TokenStream2
+    {
+        match self {
+            Kind::Primitive(ident) => quote!{ ::core::mem::size_of::<#ident>() },
+            Kind::Bool | Kind::Enum(_) => quote!{ 1 },
+            Kind::Nested(ty) => quote!{ <#ty>::size() },
+        }
+    }
+
+
+    fn read(&self, name: &LitStr) -> TokenStream2
+    {
+        match self {
+            Kind::Primitive(ident) => {
+                let getter = Ident::new(&format!("get_{}", ident), ident.span());
+                quote!{ ext.#getter()? }
+            },
+            Kind::Bool => quote!{ (ext.get_u8()? != 0) },
+            Kind::Enum(ty) => quote!{ ::e_drone::protocol::enum_from_u8::<#ty>(#name, ext.get_u8()?)? },
+            Kind::Nested(ty) => quote!{ <#ty>::parse(ext.get_slice(<#ty>::size())?)? },
+        }
+    }
+
+
+    // value는 복사 가능한 값이거나 참조를 역참조한 식
+    fn write(&self, value: TokenStream2) -> TokenStream2
+    {
+        match self {
+            Kind::Primitive(_) => quote!{ vec_data.extend_from_slice(&(#value).to_le_bytes()); },
+            Kind::Bool => quote!{ vec_data.push((#value) as u8); },
+            Kind::Enum(_) => quote!{ vec_data.push(u8::from(#value)); },
+            Kind::Nested(_) => quote!{ vec_data.extend_from_slice(&::e_drone::protocol::Serializable::to_vec(&(#value))); },
+        }
+    }
+}
+
+
+// -- Layout ----------------------------------------------------------------------------------------------
+// 필드 하나의 배치
+enum Layout {
+    Single(Kind),
+    Array(Kind, Box<Type>, Box<Expr>),  // [T; N]
+    Vec(Kind, Box<Expr>),               // #[protocol(array = N)] Vec<T>
+    TailString,                         // #[protocol(tail)] String
+    TailVec(Kind),                      // #[protocol(tail)] Vec<T>
+}
+
+
+#[derive(Default)]
+struct FieldAttribute {
+    is_enum: bool,
+    array: Option<Expr>,
+    is_tail: bool,
+}
+
+
+impl FieldAttribute {
+    fn parse(field: &Field) -> syn::Result<FieldAttribute>
+    {
+        let mut attribute = FieldAttribute::default();
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("protocol")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("enum") {
+                    attribute.is_enum = true;
+                    Ok(())
+                }
+                else if meta.path.is_ident("array") {
+                    attribute.array = Some(meta.value()?.parse()?);
+                    Ok(())
+                }
+                else if meta.path.is_ident("tail") {
+                    attribute.is_tail = true;
+                    Ok(())
+                }
+                else {
+                    Err(meta.error("expected `enum`, `array = N` or `tail`"))
+                }
+            })?;
+        }
+
+        Ok(attribute)
+    }
+}
+
+
+// Vec<T>의 T
+fn get_vec_element(ty: &Type) -> Option<&Type>
+{
+    if let Type::Path(type_path) = ty {
+        let segment = type_path.path.segments.last()?;
+        if segment.ident == "Vec" {
+            if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if let Some(GenericArgument::Type(ty_element)) = arguments.args.first() {
+                    return Some(ty_element);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+
+fn is_string(ty: &Type) -> bool
+{
+    match ty {
+        Type::Path(type_path) => type_path.path.is_ident("String"),
+        _ => false,
+    }
+}
+
+
+impl Layout {
+    fn from_field(field: &Field) -> syn::Result<Layout>
+    {
+        let attribute = FieldAttribute::parse(field)?;
+        let ty = &field.ty;
+
+        if attribute.is_tail {
+            if is_string(ty) {
+                return Ok(Layout::TailString);
+            }
+
+            return match get_vec_element(ty) {
+                Some(ty_element) => Ok(Layout::TailVec(Kind::from_type(ty_element, attribute.is_enum))),
+                None => Err(Error::new_spanned(ty, "#[protocol(tail)] requires Vec<T> or String")),
+            };
+        }
+
+        if let Some(length) = attribute.array {
+            return match get_vec_element(ty) {
+                Some(ty_element) => Ok(Layout::Vec(Kind::from_type(ty_element, attribute.is_enum), Box::new(length))),
+                None => Err(Error::new_spanned(ty, "#[protocol(array = N)] requires Vec<T>")),
+            };
+        }
+
+        if let Type::Array(type_array) = ty {
+            return Ok(Layout::Array(Kind::from_type(&type_array.elem, attribute.is_enum), type_array.elem.clone(), Box::new(type_array.len.clone())));
+        }
+
+        Ok(Layout::Single(Kind::from_type(ty, attribute.is_enum)))
+    }
+
+
+    fn is_tail(&self) -> bool
+    {
+        matches!(self, Layout::TailString | Layout::TailVec(_))
+    }
+
+
+    // 크기가 0인 원소는 남은 데이터를 줄이지 못해 TailVec을 읽는 반복이 끝나지 않으므로 컴파일할 때 확인
+    // 구조체의 size()는 매크로를 확장할 때 알 수 없으므로 const 평가로 확인
+    fn check(&self) -> Option<TokenStream2>
+    {
+        match self {
+            Layout::TailVec(Kind::Nested(ty)) => Some(quote!{
+                const _: () = assert!(<#ty>::size() > 0, "#[protocol(tail)] requires an element with non-zero size()");
+            }),
+            _ => None,
+        }
+    }
+
+
+    fn size(&self) -> TokenStream2
+    {
+        match self {
+            Layout::Single(kind) => kind.size(),
+            Layout::Array(kind, _, length) | Layout::Vec(kind, length) => {
+                let size = kind.size();
+                quote!{ (#size) * (#length) }
+            },
+            Layout::TailString | Layout::TailVec(_) => quote!{ 0 },
+        }
+    }
+
+
+    fn read(&self, name: &LitStr) -> TokenStream2
+    {
+        match self {
+            Layout::Single(kind) => kind.read(name),
+            Layout::Array(kind, ty_element, length) => {
+                let read = kind.read(name);
+                quote!{
+                    {
+                        let mut vec_value: Vec<#ty_element> = Vec::with_capacity(#length);
+                        for _ in 0..(#length) {
+                            vec_value.push(#read);
+                        }
+                        match <[#ty_element; #length] as ::core::convert::TryFrom<Vec<#ty_element>>>::try_from(vec_value) {
+                            Ok(array) => array,
+                            Err(_) => unreachable!(),
+                        }
+                    }
+                }
+            },
+            Layout::Vec(kind, length) => {
+                let read = kind.read(name);
+                quote!{
+                    {
+                        let mut vec_value = Vec::with_capacity(#length);
+                        for _ in 0..(#length) {
+                            vec_value.push(#read);
+                        }
+                        vec_value
+                    }
+                }
+            },
+            Layout::TailString => quote!{
+                String::from_utf8_lossy(ext.get_slice(ext.remaining())?).into_owned()
+            },
+            Layout::TailVec(kind) => {
+                let read = kind.read(name);
+                quote!{
+                    {
+                        let mut vec_value = Vec::new();
+                        while ext.remaining() > 0 {
+                            vec_value.push(#read);
+                        }
+                        vec_value
+                    }
+                }
+            },
+        }
+    }
+
+
+    fn write(&self, member: &Ident) -> TokenStream2
+    {
+        match self {
+            Layout::Single(kind) => kind.write(quote!{ self.#member }),
+            Layout::Array(kind, _, _) | Layout::TailVec(kind) => {
+                let write = kind.write(quote!{ *value });
+                quote!{
+                    for value in self.#member.iter() {
+                        #write
+                    }
+                }
+            },
+            // parse는 항상 N개를 읽으므로 Vec의 길이와 관계없이 N개 크기로 씀
+            Layout::Vec(kind, length) => {
+                let write = kind.write(quote!{ *value });
+                let size = kind.size();
+                quote!{
+                    {
+                        let length_start = vec_data.len();
+                        for value in self.#member.iter().take(#length) {
+                            #write
+                        }
+                        vec_data.resize(length_start + (#size) * (#length), 0);
+                    }
+                }
+            },
+            Layout::TailString => quote!{ vec_data.extend_from_slice(self.#member.as_bytes()); },
+        }
+    }
+}
+
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2>
+{
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "#[derive(Protocol)] does not support generics"));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(name, "#[derive(Protocol)] requires named fields")),
+        },
+        _ => return Err(Error::new_spanned(name, "#[derive(Protocol)] supports structs only")),
+    };
+
+    let mut vec_member: Vec<Ident> = Vec::new();
+    let mut vec_layout: Vec<Layout> = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let layout = Layout::from_field(field)?;
+
+        if layout.is_tail() && index + 1 != fields.len() {
+            return Err(Error::new_spanned(field, "#[protocol(tail)] must be the last field"));
+        }
+
+        vec_member.push(field.ident.clone().unwrap());
+        vec_layout.push(layout);
+    }
+
+    let has_tail = vec_layout.last().map(|layout| layout.is_tail()).unwrap_or(false);
+
+    let vec_size: Vec<TokenStream2> = vec_layout.iter().map(|layout| layout.size()).collect();
+
+    let vec_read: Vec<TokenStream2> = vec_member.iter().zip(vec_layout.iter())
+        .map(|(member, layout)| {
+            let name = LitStr::new(&format!("{}::{}", input.ident, member), Span::call_site());
+            let read = layout.read(&name);
+            quote!{ #member: #read, }
+        })
+        .collect();
+
+    let vec_write: Vec<TokenStream2> = vec_member.iter().zip(vec_layout.iter())
+        .map(|(member, layout)| layout.write(member))
+        .collect();
+
+    let vec_check: Vec<TokenStream2> = vec_layout.iter().filter_map(|layout| layout.check()).collect();
+
+    let check_length = if has_tail {
+        quote!{
+            if slice_data.len() < #name::size() {
+                return Err(::e_drone::protocol::ProtocolError::Truncated{ expected: #name::size(), actual: slice_data.len() });
+            }
+        }
+    }
+    else {
+        quote!{
+            if slice_data.len() != #name::size() {
+                return Err(::e_drone::protocol::ProtocolError::LengthMismatch{ expected: #name::size(), actual: slice_data.len() });
+            }
+        }
+    };
+
+    Ok(quote!{
+        #(#vec_check)*
+
+
+        impl #name {
+            pub const fn size() -> usize { 0 #(+ #vec_size)* }
+
+
+            pub fn parse(slice_data: &[u8]) -> Result<#name, ::e_drone::protocol::ProtocolError> {
+                #check_length
+
+                let mut ext = ::e_drone::communication::extractor::CheckedExtractor::new(slice_data);
+                let data = #name{
+                    #(#vec_read)*
+                };
+                ext.expect_end()?;
+
+                Ok(data)
+            }
+        }
+
+
+        impl ::e_drone::protocol::Serializable for #name {
+            fn to_vec(&self) -> Vec<u8> {
+                let mut vec_data : Vec<u8> = Vec::with_capacity(#name::size());
+
+                #(#vec_write)*
+
+                vec_data
+            }
+        }
+    })
+}
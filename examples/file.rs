@@ -6,7 +6,8 @@ use e_drone::{*};
 fn main() {
     let mut eb: file::EncryptedBinary = file::EncryptedBinary::new();
 
-    eb.read("fw_drone_4_drone_p6_20.8.13_20200818.eb");
-
-    println!("{:?}", eb.header);
+    match eb.read("fw_drone_4_drone_p6_20.8.13_20200818.eb") {
+        Ok(()) => println!("{:?}", eb.header),
+        Err(e) => println!("{}", e),
+    }
 }
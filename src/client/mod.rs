@@ -0,0 +1,741 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use crate::communication::{handler, messaging, transfer};
+use crate::communication::receiver::Receiver;
+use crate::protocol::{Data, DataType, Header, Payload, Profile, Registration, Response, SystemCount};
+use crate::protocol::{buzzer, sensor};
+use crate::protocol::command::CommandType;
+use crate::protocol::display::{Align, Font, Line, Pixel};
+use crate::protocol::external;
+use crate::system::{DeviceType, FlightEvent, Headless};
+
+
+// stream에 쌓아둘 수 있는 데이터 수
+const CAPACITY_STREAM: usize = 1024;
+
+
+// -- DataStream ----------------------------------------------------------------------------------------------
+// 백그라운드 수신 작업에서 해석한 데이터를 순서대로 전달
+// 가득 차면 새로 받은 데이터는 버림 (DroneClient::get_count_dropped()로 확인)
+pub struct DataStream {
+    rx: mpsc::Receiver<(Header, Data)>,
+}
+
+
+impl DataStream {
+    pub async fn recv(&mut self) -> Option<(Header, Data)>
+    {
+        self.rx.recv().await
+    }
+}
+
+
+impl Stream for DataStream
+{
+    type Item = (Header, Data);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>
+    {
+        self.rx.poll_recv(cx)
+    }
+}
+
+
+// -- Waiter ----------------------------------------------------------------------------------------------
+// request_and_wait()에서 응답을 기다리는 요청
+// 수신 작업은 조건에 맞는 데이터를 stream 대신 tx로 전달
+struct Waiter {
+    data_type: DataType,
+    from: DeviceType,
+    is_response: fn(&Data) -> bool,
+    tx: oneshot::Sender<Data>,
+}
+
+
+// -- Shared ----------------------------------------------------------------------------------------------
+// DroneClient와 수신 작업이 함께 사용하는 상태
+struct Shared {
+    vec_waiter: Vec<Waiter>,
+    flag_closed: bool,                  // 수신 작업이 끝나 더 이상 응답을 받을 수 없음
+    error_receive: Option<io::Error>,   // 수신 작업을 끝낸 오류
+    count_dropped: usize,               // stream이 가득 차서 버린 데이터 수
+}
+
+
+// -- DroneClient ----------------------------------------------------------------------------------------------
+// tokio 런타임에서 사용하는 비동기 클라이언트
+// 수신은 백그라운드 작업이 Receiver와 handler::check_raw로 처리하고, 결과는 stream으로 전달됨
+// 0xE0 ~ 0xE2는 생성할 때 지정한 Profile에 따라 해석 (기본값 Profile::Uwb)
+// request_and_wait()로 받은 응답은 stream으로 전달되지 않음
+// stream을 읽지 않아도 응답은 받을 수 있으며, 그동안 쌓인 데이터는 CAPACITY_STREAM개까지만 보관
+pub struct DroneClient<W> {
+    writer: W,
+    task_read: JoinHandle<()>,
+    shared: Arc<Mutex<Shared>>,
+    pub stream: DataStream,
+}
+
+
+impl<S> DroneClient<WriteHalf<S>>
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    pub fn from_stream(stream: S) -> DroneClient<WriteHalf<S>>
+    {
+        let (reader, writer): (ReadHalf<S>, WriteHalf<S>) = tokio::io::split(stream);
+
+        DroneClient::new(reader, writer)
+    }
+
+
+    pub fn from_stream_with_profile(stream: S, profile: Profile) -> DroneClient<WriteHalf<S>>
+    {
+        let (reader, writer): (ReadHalf<S>, WriteHalf<S>) = tokio::io::split(stream);
+
+        DroneClient::with_profile(reader, writer, profile)
+    }
+}
+
+
+// 명령 함수는 Drone과 같은 인자를 사용하므로 인자가 많은 함수가 있음
+#[allow(clippy::too_many_arguments)]
+impl<W: AsyncWrite + Unpin> DroneClient<W> {
+    pub fn new<R>(reader: R, writer: W) -> DroneClient<W>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        DroneClient::with_profile(reader, writer, Profile::default())
+    }
+
+
+    pub fn with_profile<R>(reader: R, writer: W, profile: Profile) -> DroneClient<W>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        DroneClient::with_receiver(reader, writer, Receiver::new(), profile)
+    }
+
+
+    // 설정을 마친 Receiver를 수신 작업에 사용 (lenient 모드, Clock 지정 등)
+    pub fn with_receiver<R>(reader: R, writer: W, receiver: Receiver, profile: Profile) -> DroneClient<W>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(CAPACITY_STREAM);
+        let shared = Arc::new(Mutex::new(Shared{ vec_waiter: Vec::new(), flag_closed: false, error_receive: None, count_dropped: 0 }));
+        let shared_read = Arc::clone(&shared);
+
+        DroneClient{
+            writer,
+            task_read: tokio::spawn(async move {
+                let result = read(reader, receiver, profile, &shared_read, &tx).await;
+
+                // 기다리던 요청은 모두 실패 처리. stream이 끝나기 전에 기록하도록 tx는 마지막에 버림
+                let mut shared = shared_read.lock().unwrap();
+                shared.vec_waiter.clear();
+                shared.flag_closed = true;
+                shared.error_receive = result.err();
+                drop(shared);
+                drop(tx);
+            }),
+            shared,
+            stream: DataStream{ rx },
+        }
+    }
+
+
+    pub fn is_finished(&self) -> bool
+    {
+        self.task_read.is_finished()
+    }
+
+
+    // 수신 작업을 끝낸 오류. 연결이 정상적으로 끊어졌거나 아직 수신 중이면 None
+    pub fn take_error_receive(&mut self) -> Option<io::Error>
+    {
+        self.shared.lock().unwrap().error_receive.take()
+    }
+
+
+    // stream이 가득 차서 버린 데이터 수
+    pub fn get_count_dropped(&self) -> usize
+    {
+        self.shared.lock().unwrap().count_dropped
+    }
+
+
+    pub async fn transfer(&mut self, vec_data: &[u8]) -> io::Result<()>
+    {
+        self.writer.write_all(vec_data).await?;
+        self.writer.flush().await
+    }
+
+
+    // -- Send ----------------------------------------------------------------------------------------------
+    pub async fn send<P: Payload>(&mut self, data: &P) -> io::Result<()>
+    {
+        self.send_to(DeviceType::Drone, data).await
+    }
+
+    pub async fn send_to<P: Payload>(&mut self, target: DeviceType, data: &P) -> io::Result<()>
+    {
+        self.transfer(&transfer::build(DeviceType::Base, target, data)).await
+    }
+
+
+    // -- Request ----------------------------------------------------------------------------------------------
+    pub async fn request(&mut self, target: DeviceType, data_type: DataType) -> io::Result<()>
+    {
+        self.transfer(&transfer::request(target, data_type)).await
+    }
+
+    // 데이터를 요청하고 응답을 받을 때까지 대기
+    // 응답을 기다리는 동안 받은 다른 데이터는 stream으로 전달
+    pub async fn request_and_wait<R: Response>(&mut self, target: DeviceType, timeout: Duration) -> io::Result<R>
+    {
+        let (tx, rx) = oneshot::channel();
+
+        // 요청을 보내기 전에 등록해야 빠른 응답을 놓치지 않음
+        {
+            let mut shared = self.shared.lock().unwrap();
+            if shared.flag_closed || self.task_read.is_finished() {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("Connection closed before requesting {:?} from {:?}", R::DATA_TYPE, target)));
+            }
+
+            shared.vec_waiter.push(Waiter{
+                data_type: R::DATA_TYPE,
+                from: target,
+                is_response: |data| R::from_data(data).is_some(),
+                tx,
+            });
+        }
+
+        self.request(target, R::DATA_TYPE).await?;
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(data)) => R::from_data(&data).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("Unexpected response: {:?}", data))),
+            Ok(Err(_)) => Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("Connection closed while waiting for {:?} from {:?}", R::DATA_TYPE, target))),
+            Err(_) => Err(io::Error::new(io::ErrorKind::TimedOut, format!("No response for {:?} from {:?}", R::DATA_TYPE, target))),
+        }
+    }
+
+    pub async fn request_system_count(&mut self, target: DeviceType, timeout: Duration) -> io::Result<SystemCount>
+    {
+        self.request_and_wait(target, timeout).await
+    }
+
+    pub async fn request_registration(&mut self, target: DeviceType, timeout: Duration) -> io::Result<Registration>
+    {
+        self.request_and_wait(target, timeout).await
+    }
+
+    pub async fn request_altitude(&mut self, timeout: Duration) -> io::Result<sensor::Altitude>
+    {
+        self.request_and_wait(DeviceType::Drone, timeout).await
+    }
+
+
+    // -- Command ----------------------------------------------------------------------------------------------
+    pub async fn command(&mut self, target: DeviceType, command_type: CommandType, option: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::command(target, command_type, option)).await
+    }
+
+
+    // -- FlightEvent ----------------------------------------------------------------------------------------------
+    pub async fn flight_event(&mut self, event: FlightEvent) -> io::Result<()>
+    {
+        self.transfer(&transfer::flight_event(event)).await
+    }
+
+    pub async fn takeoff(&mut self) -> io::Result<()>
+    {
+        self.transfer(&transfer::takeoff()).await
+    }
+
+    pub async fn landing(&mut self) -> io::Result<()>
+    {
+        self.transfer(&transfer::landing()).await
+    }
+
+    pub async fn stop(&mut self) -> io::Result<()>
+    {
+        self.transfer(&transfer::stop()).await
+    }
+
+
+    // -- Setup ----------------------------------------------------------------------------------------------
+    pub async fn set_default(&mut self) -> io::Result<()>
+    {
+        self.transfer(&transfer::set_default()).await
+    }
+
+    pub async fn set_mode_control_flight(&mut self) -> io::Result<()>
+    {
+        self.transfer(&transfer::set_mode_control_flight()).await
+    }
+
+    pub async fn headless(&mut self, headless: Headless) -> io::Result<()>
+    {
+        self.transfer(&transfer::headless(headless)).await
+    }
+
+    pub async fn clear_bias(&mut self) -> io::Result<()>
+    {
+        self.transfer(&transfer::clear_bias()).await
+    }
+
+    pub async fn clear_trim(&mut self) -> io::Result<()>
+    {
+        self.transfer(&transfer::clear_trim()).await
+    }
+
+    pub async fn trim(&mut self, roll: i16, pitch: i16, yaw: i16, throttle: i16) -> io::Result<()>
+    {
+        self.transfer(&transfer::trim(roll, pitch, yaw, throttle)).await
+    }
+
+    pub async fn set_weight(&mut self, weight: f32) -> io::Result<()>
+    {
+        self.transfer(&transfer::set_weight(weight)).await
+    }
+
+
+    // -- Echo ----------------------------------------------------------------------------------------------
+    pub async fn echo(&mut self, target: DeviceType, slice_data: &[u8]) -> io::Result<()>
+    {
+        self.transfer(&transfer::echo(target, slice_data)).await
+    }
+
+
+    // -- Control ----------------------------------------------------------------------------------------------
+    pub async fn control(&mut self, roll: i8, pitch: i8, yaw: i8, throttle: i8) -> io::Result<()>
+    {
+        self.transfer(&transfer::control(roll, pitch, yaw, throttle)).await
+    }
+
+    pub async fn control_request(&mut self, roll: i8, pitch: i8, yaw: i8, throttle: i8, data_type: DataType) -> io::Result<()>
+    {
+        self.transfer(&transfer::control_request(roll, pitch, yaw, throttle, data_type)).await
+    }
+
+    pub async fn control_position(&mut self, x: f32, y: f32, z: f32, velocity: f32, heading: i16, rotational_velocity: i16) -> io::Result<()>
+    {
+        self.transfer(&transfer::control_position(x, y, z, velocity, heading, rotational_velocity)).await
+    }
+
+
+    // -- Battle ----------------------------------------------------------------------------------------------
+    pub async fn battle_ir_message(&mut self, ir_message: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::battle_ir_message(ir_message)).await
+    }
+
+    pub async fn battle_light_event_command(&mut self, target: DeviceType, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8, command_type: CommandType, option: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::battle_light_event_command(target, event, interval, repeat, r, g, b, command_type, option)).await
+    }
+
+    pub async fn battle_ir_message_light_event_command(&mut self, target: DeviceType, ir_message: u8, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8, command_type: CommandType, option: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::battle_ir_message_light_event_command(target, ir_message, event, interval, repeat, r, g, b, command_type, option)).await
+    }
+
+
+    // -- Light ----------------------------------------------------------------------------------------------
+    pub async fn light_manual(&mut self, target: DeviceType, flags: u16, brightness: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::light_manual(target, flags, brightness)).await
+    }
+
+    pub async fn light_mode(&mut self, target: DeviceType, mode: u8, interval: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::light_mode(target, mode, interval)).await
+    }
+
+    pub async fn light_event(&mut self, target: DeviceType, event: u8, interval: u16, repeat: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::light_event(target, event, interval, repeat)).await
+    }
+
+    pub async fn light_mode_color(&mut self, target: DeviceType, mode: u8, interval: u16, r: u8, g: u8, b: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::light_mode_color(target, mode, interval, r, g, b)).await
+    }
+
+    pub async fn light_event_color(&mut self, target: DeviceType, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::light_event_color(target, event, interval, repeat, r, g, b)).await
+    }
+
+    pub async fn light_default(&mut self, target: DeviceType, mode: u8, interval: u16, r: u8, g: u8, b: u8) -> io::Result<()>
+    {
+        self.transfer(&transfer::light_default(target, mode, interval, r, g, b)).await
+    }
+
+
+    // -- Buzzer ----------------------------------------------------------------------------------------------
+    pub async fn buzzer_stop(&mut self, target: DeviceType) -> io::Result<()>
+    {
+        self.transfer(&transfer::buzzer_stop(target)).await
+    }
+
+    pub async fn buzzer_scale(&mut self, target: DeviceType, scale: buzzer::Scale, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::buzzer_scale(target, scale, time)).await
+    }
+
+    pub async fn buzzer_scale_reserve(&mut self, target: DeviceType, scale: buzzer::Scale, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::buzzer_scale_reserve(target, scale, time)).await
+    }
+
+    pub async fn buzzer_hz(&mut self, target: DeviceType, hz: u16, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::buzzer_hz(target, hz, time)).await
+    }
+
+    pub async fn buzzer_hz_reserve(&mut self, target: DeviceType, hz: u16, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::buzzer_hz_reserve(target, hz, time)).await
+    }
+
+    pub async fn buzzer_mute(&mut self, target: DeviceType, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::buzzer_mute(target, time)).await
+    }
+
+    pub async fn buzzer_mute_reserve(&mut self, target: DeviceType, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::buzzer_mute_reserve(target, time)).await
+    }
+
+
+    // -- Vibrator ----------------------------------------------------------------------------------------------
+    pub async fn vibrator(&mut self, on: u16, off: u16, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::vibrator(on, off, time)).await
+    }
+
+    pub async fn vibrator_reserve(&mut self, on: u16, off: u16, time: u16) -> io::Result<()>
+    {
+        self.transfer(&transfer::vibrator_reserve(on, off, time)).await
+    }
+
+
+    // -- Display ----------------------------------------------------------------------------------------------
+    pub async fn draw_clear_all(&mut self, pixel: Pixel) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_clear_all(pixel)).await
+    }
+
+    pub async fn draw_clear(&mut self, x: i16, y: i16, width: i16, height: i16, pixel: Pixel) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_clear(x, y, width, height, pixel)).await
+    }
+
+    pub async fn draw_invert(&mut self, x: i16, y: i16, width: i16, height: i16) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_invert(x, y, width, height)).await
+    }
+
+    pub async fn draw_point(&mut self, x: i16, y: i16, pixel: Pixel) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_point(x, y, pixel)).await
+    }
+
+    pub async fn draw_line(&mut self, x1: i16, y1: i16, x2: i16, y2: i16, pixel: Pixel, line: Line) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_line(x1, y1, x2, y2, pixel, line)).await
+    }
+
+    pub async fn draw_rect(&mut self, x: i16, y: i16, width: i16, height: i16, pixel: Pixel, fill: bool, line: Line) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_rect(x, y, width, height, pixel, fill, line)).await
+    }
+
+    pub async fn draw_circle(&mut self, x: i16, y: i16, radius: i16, pixel: Pixel, fill: bool) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_circle(x, y, radius, pixel, fill)).await
+    }
+
+    pub async fn draw_string(&mut self, x: i16, y: i16, font: Font, pixel: Pixel, string: String) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_string(x, y, font, pixel, string)).await
+    }
+
+    pub async fn draw_string_align(&mut self, x_start: i16, x_end: i16, y: i16, align: Align, font: Font, pixel: Pixel, string: String) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_string_align(x_start, x_end, y, align, font, pixel, string)).await
+    }
+
+    pub async fn draw_image(&mut self, x: i16, y: i16, width: i16, height: i16, vec_image: Vec<u8>) -> io::Result<()>
+    {
+        self.transfer(&transfer::draw_image(x, y, width, height, vec_image)).await
+    }
+
+
+    // -- GpsRtk ----------------------------------------------------------------------------------------------
+    // 이동국 상태는 request_and_wait::<navigation::RtkNavigationState>로 확인
+    pub async fn gps_rtk_base(&mut self, enable: bool) -> io::Result<()>
+    {
+        self.transfer(&transfer::gps_rtk_base(enable)).await
+    }
+
+    pub async fn gps_rtk_rover(&mut self, enable: bool) -> io::Result<()>
+    {
+        self.transfer(&transfer::gps_rtk_rover(enable)).await
+    }
+
+
+    // -- External ----------------------------------------------------------------------------------------------
+    // 상태를 받으려면 Profile::External로 생성
+    pub async fn external_system_command(&mut self, target: DeviceType, command_type: external::system::CommandType) -> io::Result<()>
+    {
+        self.transfer(&transfer::external_system_command(target, command_type)).await
+    }
+
+    pub async fn external_camera_command(&mut self, target: DeviceType, command_type: external::camera::CommandType) -> io::Result<()>
+    {
+        self.transfer(&transfer::external_camera_command(target, command_type)).await
+    }
+}
+
+
+// 연결이 끊어지거나 DataStream이 버려질 때까지 데이터를 읽어 해석
+async fn read<R>(mut reader: R, mut receiver: Receiver, profile: Profile, shared: &Mutex<Shared>, tx: &mpsc::Sender<(Header, Data)>) -> io::Result<()>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = [0u8; 1024];
+
+    loop
+    {
+        let length = reader.read(&mut buffer).await?;
+        if length == 0 {
+            return Ok(());
+        }
+
+        receiver.push_slice(&buffer[..length]);
+
+        while let messaging::State::Loaded = receiver.check()
+        {
+            receiver.clear();
+
+            let header = *receiver.get_header();
+            let data = handler::check_raw(profile, receiver.get_header_raw(), receiver.get_data())
+                .unwrap_or_else(|e| Data::ErrorMessage(e.to_string()));
+
+            if let Some(data) = deliver(shared, &header, data)
+            {
+                match tx.try_send((header, data)) {
+                    Ok(()) => {},
+                    Err(mpsc::error::TrySendError::Full(_)) => shared.lock().unwrap().count_dropped += 1,
+                    Err(mpsc::error::TrySendError::Closed(_)) => return Ok(()),
+                }
+            }
+        }
+    }
+}
+
+
+// 응답을 기다리는 요청이 있으면 전달하고, 없으면 데이터를 그대로 반환
+fn deliver(shared: &Mutex<Shared>, header: &Header, data: Data) -> Option<Data>
+{
+    let vec_waiter = &mut shared.lock().unwrap().vec_waiter;
+
+    // 시간 초과 등으로 더 이상 기다리지 않는 요청은 정리
+    vec_waiter.retain(|waiter| !waiter.tx.is_closed());
+
+    match vec_waiter.iter().position(|waiter| waiter.data_type == header.data_type && waiter.from == header.from && (waiter.is_response)(&data)) {
+        Some(index) => vec_waiter.remove(index).tx.send(data).err(),
+        None => Some(data),
+    }
+}
+
+
+impl<W> Drop for DroneClient<W>
+{
+    fn drop(&mut self)
+    {
+        self.task_read.abort();
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn duplex() {
+        let (stream_client, mut stream_drone) = tokio::io::duplex(1024);
+        let mut client = DroneClient::from_stream(stream_client);
+
+        // 전송
+        client.takeoff().await.unwrap();
+
+        let vec_takeoff = transfer::takeoff();
+        let mut buffer = vec![0u8; vec_takeoff.len()];
+        stream_drone.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(buffer, vec_takeoff);
+
+        // 수신 (앞에 쓰레기 데이터가 섞인 경우 포함)
+        let attitude = sensor::Attitude{roll: 10, pitch: -20, yaw: 30};
+        let mut vec_data = vec![0x00, 0xFF];
+        vec_data.extend(transfer::transfer(DataType::Attitude, DeviceType::Drone, DeviceType::Base, &crate::protocol::Serializable::to_vec(&attitude)));
+        stream_drone.write_all(&vec_data).await.unwrap();
+
+        let (header, data) = client.stream.recv().await.unwrap();
+        assert_eq!(header.data_type, DataType::Attitude);
+        assert_eq!(header.from, DeviceType::Drone);
+
+        match data {
+            Data::Attitude(data) => {
+                assert_eq!((data.roll, data.pitch, data.yaw), (10, -20, 30));
+            },
+            _ => panic!("unexpected data: {:?}", data),
+        }
+
+        // 연결 종료
+        drop(stream_drone);
+        assert!(client.stream.recv().await.is_none());
+    }
+
+
+    #[tokio::test]
+    async fn profile() {
+        let (stream_client, mut stream_drone) = tokio::io::duplex(1024);
+        let mut client = DroneClient::from_stream_with_profile(stream_client, Profile::External);
+
+        let state = external::system::State{mode_system: external::system::ModeSystem::Run, fps: 30};
+        stream_drone.write_all(&transfer::build(DeviceType::Drone, DeviceType::Base, &state)).await.unwrap();
+
+        let (header, data) = client.stream.recv().await.unwrap();
+        assert_eq!(header.data_type, DataType::ExternalSystemState);
+        assert_eq!(data, Data::ExternalSystemState(state));
+    }
+
+
+    #[tokio::test]
+    async fn request_and_wait() {
+        let (stream_client, mut stream_drone) = tokio::io::duplex(1024);
+        let mut client = DroneClient::from_stream(stream_client);
+
+        let task_drone = tokio::spawn(async move {
+            let vec_request = transfer::request(DeviceType::Drone, DataType::Attitude);
+            let mut buffer = vec![0u8; vec_request.len()];
+            stream_drone.read_exact(&mut buffer).await.unwrap();
+            assert_eq!(buffer, vec_request);
+
+            // 조종기에서 온 Attitude는 응답이 아님
+            stream_drone.write_all(&transfer::build(DeviceType::Controller, DeviceType::Base, &sensor::Attitude{roll: 1, pitch: 2, yaw: 3})).await.unwrap();
+            stream_drone.write_all(&transfer::build(DeviceType::Drone, DeviceType::Base, &sensor::Attitude{roll: 10, pitch: 20, yaw: 30})).await.unwrap();
+            stream_drone
+        });
+
+        let attitude = client.request_and_wait::<sensor::Attitude>(DeviceType::Drone, Duration::from_secs(1)).await.unwrap();
+        assert_eq!(attitude, sensor::Attitude{roll: 10, pitch: 20, yaw: 30});
+
+        // 응답이 아닌 데이터는 stream으로 전달
+        let (header, data) = client.stream.recv().await.unwrap();
+        assert_eq!(header.from, DeviceType::Controller);
+        assert_eq!(data, Data::Attitude(sensor::Attitude{roll: 1, pitch: 2, yaw: 3}));
+
+        // 응답이 없는 경우
+        let stream_drone = task_drone.await.unwrap();
+        let error = client.request_and_wait::<sensor::Motion>(DeviceType::Drone, Duration::from_millis(10)).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+        // 연결이 끊어진 경우
+        drop(stream_drone);
+        assert!(client.stream.recv().await.is_none());
+        let error = client.request_and_wait::<sensor::Motion>(DeviceType::Drone, Duration::from_secs(1)).await.unwrap_err();
+        assert_ne!(error.kind(), io::ErrorKind::TimedOut);
+    }
+
+
+    // 항상 오류를 반환하는 입력
+    struct Broken;
+
+    impl AsyncRead for Broken {
+        fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, _buf: &mut tokio::io::ReadBuf<'_>) -> Poll<io::Result<()>>
+        {
+            Poll::Ready(Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset")))
+        }
+    }
+
+
+    #[tokio::test]
+    async fn closed() {
+        // 수신만 끝나고 전송은 가능한 경우에도 응답을 기다리지 않음
+        let (reader, stream_drone) = tokio::io::duplex(1024);
+        drop(stream_drone);
+        let mut client = DroneClient::new(reader, tokio::io::sink());
+
+        assert!(client.stream.recv().await.is_none());
+        let error = client.request_and_wait::<sensor::Motion>(DeviceType::Drone, Duration::from_secs(60)).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(client.take_error_receive().is_none());
+
+        // 수신 오류는 take_error_receive()로 확인
+        let mut client = DroneClient::new(Broken, tokio::io::sink());
+
+        assert!(client.stream.recv().await.is_none());
+        assert_eq!(client.take_error_receive().unwrap().kind(), io::ErrorKind::ConnectionReset);
+        assert!(client.take_error_receive().is_none());
+    }
+
+
+    #[tokio::test]
+    async fn stream_full() {
+        let (stream_client, mut stream_drone) = tokio::io::duplex(1024);
+        let mut client = DroneClient::from_stream(stream_client);
+
+        // stream을 읽지 않아도 수신 작업은 멈추지 않고 응답을 전달
+        let task_drone = tokio::spawn(async move {
+            let vec_attitude = transfer::build(DeviceType::Drone, DeviceType::Base, &sensor::Attitude{roll: 1, pitch: 2, yaw: 3});
+            for _ in 0..(CAPACITY_STREAM + 10) {
+                stream_drone.write_all(&vec_attitude).await.unwrap();
+            }
+            stream_drone.write_all(&transfer::build(DeviceType::Drone, DeviceType::Base, &sensor::Motion::new())).await.unwrap();
+            stream_drone
+        });
+
+        let motion = client.request_and_wait::<sensor::Motion>(DeviceType::Drone, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(motion, sensor::Motion::new());
+        assert_eq!(client.get_count_dropped(), 10);
+
+        let _stream_drone = task_drone.await.unwrap();
+        for _ in 0..CAPACITY_STREAM {
+            assert!(matches!(client.stream.recv().await, Some((_, Data::Attitude(_)))));
+        }
+    }
+
+
+    #[tokio::test]
+    async fn lenient() {
+        let (stream_client, stream_drone) = tokio::io::duplex(1024);
+        let (reader, writer) = tokio::io::split(stream_client);
+        let mut receiver = Receiver::new();
+        receiver.set_lenient(true);
+        let mut client = DroneClient::with_receiver(reader, writer, receiver, Profile::default());
+
+        let (_, mut writer_drone) = tokio::io::split(stream_drone);
+        writer_drone.write_all(&transfer::transfer_raw(&[0xFF, 0x03, 0x10, 0x55], &[1, 2, 3])).await.unwrap();
+
+        let (header, data) = client.stream.recv().await.unwrap();
+        assert_eq!(header.data_type, DataType::None);
+        assert_eq!(data, Data::Unknown{ header_raw: [0xFF, 0x03, 0x10, 0x55], payload: vec![1, 2, 3] });
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+
+// -- Clock ----------------------------------------------------------------------------------------------
+// 수신 시간 제한, 연결 상태 확인 등에 사용하는 시계
+// now()는 임의의 기준 시점 이후의 경과 시간을 반환하며 줄어들지 않아야 함
+pub trait Clock: Debug + Send + Sync
+{
+    fn now(&self) -> Duration;
+}
+
+
+// -- SystemClock ----------------------------------------------------------------------------------------------
+// 실제 시간 (Instant 사용, 시스템 시각이 변경되어도 거꾸로 가지 않음)
+#[derive(Debug)]
+pub struct SystemClock {
+    time_base: Instant,
+}
+
+
+impl SystemClock {
+    pub fn new() -> SystemClock
+    {
+        SystemClock{
+            time_base: Instant::now(),
+        }
+    }
+}
+
+
+impl Default for SystemClock {
+    fn default() -> Self
+    {
+        SystemClock::new()
+    }
+}
+
+
+impl Clock for SystemClock
+{
+    fn now(&self) -> Duration
+    {
+        self.time_base.elapsed()
+    }
+}
+
+
+// -- ManualClock ----------------------------------------------------------------------------------------------
+// 직접 시간을 지정하는 시계 (테스트, 기록된 시각으로 재생할 때 사용)
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    time: Mutex<Duration>,
+}
+
+
+impl ManualClock {
+    pub fn new() -> ManualClock
+    {
+        ManualClock{
+            time: Mutex::new(Duration::ZERO),
+        }
+    }
+
+
+    // 이전보다 이른 시각은 무시
+    pub fn set(&self, time: Duration)
+    {
+        let mut time_now = self.time.lock().unwrap();
+        if time > *time_now {
+            *time_now = time;
+        }
+    }
+
+
+    pub fn advance(&self, duration: Duration)
+    {
+        let mut time_now = self.time.lock().unwrap();
+        *time_now += duration;
+    }
+}
+
+
+impl Clock for ManualClock
+{
+    fn now(&self) -> Duration
+    {
+        *self.time.lock().unwrap()
+    }
+}
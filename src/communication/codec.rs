@@ -0,0 +1,127 @@
+use std::io;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::communication::{handler, messaging, transfer};
+use crate::communication::receiver::Receiver;
+use crate::protocol::{Data, Header, Profile, Serializable};
+
+
+// -- FrameCodec ----------------------------------------------------------------------------------------------
+// tokio-util Framed에서 사용하는 코덱
+// 수신 데이터는 Receiver에 넣어 처리하므로 시작 코드, 헤더, CRC16 확인 방식이 Drone과 같음
+#[derive(Debug)]
+pub struct FrameCodec {
+    pub receiver: Receiver,
+    pub profile: Profile,       // 0xE0 ~ 0xE2 해석 기준
+}
+
+
+impl FrameCodec {
+    pub fn new() -> FrameCodec
+    {
+        FrameCodec::with_profile(Profile::default())
+    }
+
+
+    pub fn with_profile(profile: Profile) -> FrameCodec
+    {
+        FrameCodec{
+            receiver: Receiver::new(),
+            profile,
+        }
+    }
+}
+
+
+impl Default for FrameCodec {
+    fn default() -> Self
+    {
+        FrameCodec::new()
+    }
+}
+
+
+impl Decoder for FrameCodec
+{
+    type Item = (Header, Data);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error>
+    {
+        if !src.is_empty() {
+            self.receiver.push_slice(src);
+            src.advance(src.len());
+        }
+
+        if let messaging::State::Loaded = self.receiver.check()
+        {
+            self.receiver.clear();
+
+            let header = *self.receiver.get_header();
+            let data = handler::check_raw(self.profile, self.receiver.get_header_raw(), self.receiver.get_data())
+                .unwrap_or_else(|e| Data::ErrorMessage(e.to_string()));
+
+            return Ok(Some((header, data)));
+        }
+
+        Ok(None)
+    }
+}
+
+
+// 헤더의 length는 무시하고 실제 데이터 길이를 사용
+impl<T: Serializable> Encoder<(Header, T)> for FrameCodec
+{
+    type Error = io::Error;
+
+    fn encode(&mut self, item: (Header, T), dst: &mut BytesMut) -> Result<(), Self::Error>
+    {
+        let (header, data) = item;
+        let vec_data = data.to_vec();
+
+        if vec_data.len() > u8::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Data is too long"));
+        }
+
+        dst.put_slice(&transfer::transfer(header.data_type, header.from, header.to, &vec_data));
+
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{DataType, sensor};
+    use crate::system::DeviceType;
+
+    #[test]
+    fn encode_decode() {
+        let mut codec = FrameCodec::new();
+        let mut buffer = BytesMut::new();
+
+        let header = Header{data_type: DataType::Attitude, length: 0, from: DeviceType::Drone, to: DeviceType::Base};
+        codec.encode((header, sensor::Attitude{roll: 1, pitch: 2, yaw: 3}), &mut buffer).unwrap();
+        codec.encode((header, sensor::Attitude{roll: 4, pitch: 5, yaw: 6}), &mut buffer).unwrap();
+
+        // 나누어 도착하는 경우
+        let mut buffer_first = buffer.split_to(7);
+        assert!(codec.decode(&mut buffer_first).unwrap().is_none());
+        assert!(buffer_first.is_empty());
+
+        let mut vec_roll = Vec::new();
+        while let Some((header, data)) = codec.decode(&mut buffer).unwrap() {
+            assert_eq!(header.data_type, DataType::Attitude);
+            assert_eq!(header.length, 6);
+            if let Data::Attitude(attitude) = data {
+                vec_roll.push(attitude.roll);
+            }
+        }
+
+        assert_eq!(vec_roll, vec![1, 4]);
+    }
+}
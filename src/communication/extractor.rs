@@ -15,6 +15,8 @@
 
 use byteorder::{ByteOrder, LittleEndian};
 
+use crate::protocol::ProtocolError;
+
 pub struct Extractor
 {
     vec_data: Vec<u8>,
@@ -168,3 +170,134 @@ impl Extractor
         self.get_length() - self.index
     }
 }
+
+
+/*
+    2026.10.18
+
+    CheckedExtractor는 데이터가 부족할 때 0을 반환하는 대신 ProtocolError를 반환.
+    길이가 잘린 데이터가 0으로 채워진 그럴듯한 값으로 해석되는 것을 막기 위함.
+    원본 슬라이스를 복사하지 않고 빌려서 사용하며,
+    parse 함수에서는 ? 연산자로 간단하게 오류를 전달할 수 있다.
+ */
+
+pub struct CheckedExtractor<'a>
+{
+    slice_data: &'a [u8],
+    index : usize,
+}
+
+impl<'a> CheckedExtractor<'a>
+{
+    pub fn new(source: &'a [u8]) -> CheckedExtractor<'a> {
+        CheckedExtractor {
+            slice_data: source,
+            index: 0,
+        }
+    }
+
+    // 남은 데이터가 length보다 짧으면 Truncated 반환
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ProtocolError>
+    {
+        if length <= self.remaining() {
+            let slice_data: &'a [u8] = &self.slice_data[self.index..(self.index + length)];
+            self.index += length;
+            Ok(slice_data)
+        }
+        else { Err(ProtocolError::Truncated{ expected: self.index + length, actual: self.slice_data.len() }) }
+    }
+
+    pub fn get_u8(&mut self) -> Result<u8, ProtocolError>
+    {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn get_u16(&mut self) -> Result<u16, ProtocolError>
+    {
+        Ok(LittleEndian::read_u16(self.take(2)?))
+    }
+
+    pub fn get_u32(&mut self) -> Result<u32, ProtocolError>
+    {
+        Ok(LittleEndian::read_u32(self.take(4)?))
+    }
+
+    pub fn get_u64(&mut self) -> Result<u64, ProtocolError>
+    {
+        Ok(LittleEndian::read_u64(self.take(8)?))
+    }
+
+    pub fn get_i8(&mut self) -> Result<i8, ProtocolError>
+    {
+        Ok(self.take(1)?[0] as i8)
+    }
+
+    pub fn get_i16(&mut self) -> Result<i16, ProtocolError>
+    {
+        Ok(LittleEndian::read_i16(self.take(2)?))
+    }
+
+    pub fn get_i32(&mut self) -> Result<i32, ProtocolError>
+    {
+        Ok(LittleEndian::read_i32(self.take(4)?))
+    }
+
+    pub fn get_i64(&mut self) -> Result<i64, ProtocolError>
+    {
+        Ok(LittleEndian::read_i64(self.take(8)?))
+    }
+
+    pub fn get_f32(&mut self) -> Result<f32, ProtocolError>
+    {
+        Ok(LittleEndian::read_f32(self.take(4)?))
+    }
+
+    pub fn get_f64(&mut self) -> Result<f64, ProtocolError>
+    {
+        Ok(LittleEndian::read_f64(self.take(8)?))
+    }
+
+    // 복사하지 않고 원본 슬라이스의 일부를 빌려줌
+    pub fn get_slice(&mut self, length: usize) -> Result<&'a [u8], ProtocolError>
+    {
+        self.take(length)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.slice_data.len() - self.index
+    }
+
+    // 읽지 않은 데이터가 남아 있으면 LengthMismatch 반환
+    pub fn expect_end(&self) -> Result<(), ProtocolError>
+    {
+        if self.remaining() == 0 {
+            Ok(())
+        }
+        else { Err(ProtocolError::LengthMismatch{ expected: self.index, actual: self.slice_data.len() }) }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_extractor() {
+        let vec_data: Vec<u8> = vec![0x01, 0x34, 0x12, 0xFF, 0xAA, 0xBB];
+        let mut ext = CheckedExtractor::new(&vec_data);
+
+        assert_eq!(ext.get_u8(), Ok(0x01));
+        assert_eq!(ext.get_u16(), Ok(0x1234));
+        assert_eq!(ext.get_i8(), Ok(-1));
+        assert_eq!(ext.remaining(), 2);
+        assert_eq!(ext.expect_end(), Err(ProtocolError::LengthMismatch{ expected: 4, actual: 6 }));
+
+        // 부족한 경우 읽지 않고 오류 반환
+        assert_eq!(ext.get_u32(), Err(ProtocolError::Truncated{ expected: 8, actual: 6 }));
+        assert_eq!(ext.get_slice(2), Ok(&vec_data[4..6]));
+        assert_eq!(ext.expect_end(), Ok(()));
+        assert_eq!(ext.get_u8(), Err(ProtocolError::Truncated{ expected: 7, actual: 6 }));
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::convert::TryFrom;
+
+use crate::communication::{crc16, handler};
+use crate::protocol::{*};
+use crate::system::DeviceType;
+
+
+// -- Frame ----------------------------------------------------------------------------------------------
+// 시작 코드(0x0A 0x55), 헤더, 데이터, CRC16으로 구성된 전송 단위
+// header_raw는 받은 헤더 원본. lenient 모드에서 받은 정의되지 않은 DataType, DeviceType도 그대로 보존하며
+// encode, CRC16 계산, to_data는 header_raw를 기준으로 함
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub header: Header,
+    pub header_raw: [u8; 4],
+    pub payload: Vec<u8>,
+    pub crc: u16,
+}
+
+
+impl Frame {
+    // 헤더의 length와 crc는 데이터에 맞춰 다시 계산
+    // 헤더의 length는 1바이트이므로 데이터가 255바이트를 넘으면 None
+    pub fn new(data_type: DataType, from: DeviceType, to: DeviceType, payload: Vec<u8>) -> Option<Frame>
+    {
+        let length = u8::try_from(payload.len()).ok()?;
+        let header = Header{ data_type, length, from, to };
+        let header_raw = [data_type.into(), length, from.into(), to.into()];
+        let crc = Frame::calc_crc(&header_raw, &payload);
+
+        Some(Frame{ header, header_raw, payload, crc })
+    }
+
+
+    pub fn from_serializable<T: Serializable>(data_type: DataType, from: DeviceType, to: DeviceType, data: &T) -> Option<Frame>
+    {
+        Frame::new(data_type, from, to, data.to_vec())
+    }
+
+
+    // Data를 전송할 수 있는 형태로 변환. 전송 대상이 아닌 Data(None, ErrorMessage, Header)나 255바이트를 넘는 데이터는 None
+    pub fn from_data(from: DeviceType, to: DeviceType, data: &Data) -> Option<Frame>
+    {
+        data.to_frame(from, to)
+    }
+
+
+    pub fn calc_crc(header_raw: &[u8; 4], payload: &[u8]) -> u16
+    {
+        let crc = crc16::calc_array(0, header_raw);
+        crc16::calc_array(crc, payload)
+    }
+
+
+    // 헤더의 길이와 CRC16이 데이터와 일치하는지 확인
+    pub fn is_valid(&self) -> bool
+    {
+        self.header_raw[1] as usize == self.payload.len() && self.crc == Frame::calc_crc(&self.header_raw, &self.payload)
+    }
+
+
+    pub fn encode(&self) -> Vec<u8>
+    {
+        let mut vec_data: Vec<u8> = Vec::with_capacity(8 + self.payload.len());
+
+        vec_data.push(0x0A);
+        vec_data.push(0x55);
+        vec_data.extend_from_slice(&self.header_raw);
+        vec_data.extend_from_slice(&self.payload);
+        vec_data.extend_from_slice(&self.crc.to_le_bytes());
+
+        vec_data
+    }
+
+
+    // 시작 코드부터 CRC16까지 하나의 프레임만 들어있어야 함
+    pub fn decode(slice_data: &[u8]) -> Result<Frame, ProtocolError>
+    {
+        if slice_data.len() < 8 {
+            return Err(ProtocolError::Truncated{ expected: 8, actual: slice_data.len() });
+        }
+
+        if slice_data[0] != 0x0A || slice_data[1] != 0x55 {
+            return Err(ProtocolError::UnknownValue{ field: "start", value: u16::from_le_bytes([slice_data[0], slice_data[1]]) as u32 });
+        }
+
+        let length = slice_data[3] as usize;
+        if slice_data.len() != 8 + length {
+            return Err(ProtocolError::LengthMismatch{ expected: 8 + length, actual: slice_data.len() });
+        }
+
+        let data_type: DataType = enum_from_u8("DataType", slice_data[2])?;
+        let from: DeviceType = enum_from_u8("DeviceType", slice_data[4])?;
+        let to: DeviceType = enum_from_u8("DeviceType", slice_data[5])?;
+        if data_type == DataType::None {
+            return Err(ProtocolError::UnknownValue{ field: "DataType", value: slice_data[2] as u32 });
+        }
+
+        let frame = Frame{
+            header: Header{ data_type, length: length as u8, from, to },
+            header_raw: [slice_data[2], slice_data[3], slice_data[4], slice_data[5]],
+            payload: slice_data[6..6 + length].to_vec(),
+            crc: u16::from_le_bytes([slice_data[6 + length], slice_data[7 + length]]),
+        };
+
+        let crc = Frame::calc_crc(&frame.header_raw, &frame.payload);
+        if frame.crc != crc {
+            return Err(ProtocolError::CrcMismatch{ expected: crc, actual: frame.crc });
+        }
+
+        Ok(frame)
+    }
+
+
+    // 정의되지 않은 DataType, DeviceType을 사용한 프레임은 Data::Unknown
+    pub fn to_data(&self) -> Result<Data, ProtocolError>
+    {
+        self.to_data_with_profile(Profile::default())
+    }
+
+
+    // 0xE0 ~ 0xE2를 장치 계열(Profile)에 맞게 해석
+    pub fn to_data_with_profile(&self, profile: Profile) -> Result<Data, ProtocolError>
+    {
+        handler::check_raw(profile, &self.header_raw, &self.payload)
+    }
+}
+
+
+impl TryFrom<&Frame> for Data {
+    type Error = ProtocolError;
+
+    fn try_from(frame: &Frame) -> Result<Data, ProtocolError>
+    {
+        frame.to_data()
+    }
+}
+
+
+impl Serializable for Frame {
+    fn to_vec(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{:?} / {:?} -> {:?} / {} / {:02X?} / CRC16: {:04X}", self.header.data_type, self.header.from, self.header.to, self.header.length, self.payload, self.crc)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::communication::transfer;
+
+    #[test]
+    fn encode_decode() {
+        let vec_data = transfer::takeoff();
+
+        let frame = Frame::decode(&vec_data).unwrap();
+        assert!(frame.is_valid());
+        assert_eq!(frame.encode(), vec_data);
+        assert_eq!(Frame::from_data(DeviceType::Base, DeviceType::Drone, &frame.to_data().unwrap()), Some(frame.clone()));
+        assert_eq!(format!("{}", frame), format!("Command / Base -> Drone / 2 / [07, 11] / CRC16: {:04X}", frame.crc));
+        assert_eq!(Frame::new(DataType::Command, DeviceType::Base, DeviceType::Drone, frame.payload.clone()), Some(frame.clone()));
+        assert_eq!(Frame::new(DataType::Command, DeviceType::Base, DeviceType::Drone, vec![0; 256]), None);
+
+        let mut vec_wrong = vec_data.clone();
+        vec_wrong[7] ^= 0xFF;
+        assert!(matches!(Frame::decode(&vec_wrong), Err(ProtocolError::CrcMismatch{ .. })));
+        assert_eq!(Frame::decode(&vec_data[1..]), Err(ProtocolError::UnknownValue{ field: "start", value: 0x1155 }));
+        assert_eq!(Frame::decode(&vec_data[..9]), Err(ProtocolError::LengthMismatch{ expected: 10, actual: 9 }));
+        assert_eq!(Frame::decode(&vec_data[..4]), Err(ProtocolError::Truncated{ expected: 8, actual: 4 }));
+    }
+}
@@ -1,115 +1,132 @@
 use crate::protocol::{*};
 
 
-pub fn check(header: &Header, vec_data: &Vec<u8>) -> Data
+pub fn check(header: &Header, vec_data: &[u8]) -> Result<Data, ProtocolError>
 {
-    if header.length != vec_data.len() as u8 {
-        return Data::ErrorMessage(format!("Handler / Data Length Error / DataType: {:?}, Length: {}, Data: {:?}", header.data_type, header.length, vec_data));
+    check_with_profile(Profile::default(), header, vec_data)
+}
+
+
+// 받은 헤더를 그대로 사용하여 해석
+// 정의되지 않은 DataType, DeviceType이 들어있으면 Data::Unknown으로 보존 (Receiver::set_lenient)
+pub fn check_raw(profile: Profile, header_raw: &[u8; 4], vec_data: &[u8]) -> Result<Data, ProtocolError>
+{
+    let header = Header::parse(header_raw)?;
+
+    if header.data_type == DataType::None || header.to_vec() != header_raw {
+        if header_raw[1] as usize != vec_data.len() {
+            return Err(ProtocolError::LengthMismatch{ expected: header_raw[1] as usize, actual: vec_data.len() });
+        }
+
+        return Ok(Data::Unknown{ header_raw: *header_raw, payload: vec_data.to_vec() });
+    }
+
+    check_with_profile(profile, &header, vec_data)
+}
+
+
+// 장치 계열에 따라 0xE0 ~ 0xE2를 다르게 해석
+pub fn check_with_profile(profile: Profile, header: &Header, vec_data: &[u8]) -> Result<Data, ProtocolError>
+{
+    if header.length as usize != vec_data.len() {
+        return Err(ProtocolError::LengthMismatch{ expected: header.length as usize, actual: vec_data.len() });
     }
 
     let length : usize = header.length as usize;
 
+    if profile == Profile::External {
+        match header.data_type {
+            // ExternalSystemState = 0xE0
+            DataType::ExternalSystemState => {
+                return external::system::State::parse(vec_data).map(Data::ExternalSystemState);
+            },
+            // ExternalSystemCommand = 0xE1
+            DataType::ExternalSystemCommand => {
+                return external::system::Command::parse(vec_data).map(Data::ExternalSystemCommand);
+            },
+            // ExternalCameraState = 0xE2
+            DataType::ExternalCameraState => {
+                return external::camera::State::parse(vec_data).map(Data::ExternalCameraState);
+            },
+            _ => {},
+        }
+    }
+
     match header.data_type {
         // Ping = 0x01
         DataType::Ping => {
-            match Ping::parse(vec_data) {
-                Ok(data) => return Data::Ping(data),
-                Err(_e) => {},
-            }
+            return Ping::parse(vec_data).map(Data::Ping);
         },
         // Ack = 0x02
         DataType::Ack => {
-            match Ack::parse(vec_data) {
-                Ok(data) => return Data::Ack(data),
-                Err(_e) => {},
-            }
+            return Ack::parse(vec_data).map(Data::Ack);
         },
         // Error = 0x03
         DataType::Error => {
-            match Error::parse(vec_data) {
-                Ok(data) => return Data::Error(data),
-                Err(_e) => {},
-            }
+            return Error::parse(vec_data).map(Data::Error);
         },
         // Request = 0x04
         DataType::Request => {
             if length == Request::size() {
-                match Request::parse(vec_data) {
-                    Ok(data) => return Data::Request(data),
-                    Err(_e) => {},
-                }
+                return Request::parse(vec_data).map(Data::Request);
             }
             else if length == RequestOption::size() {
-                match RequestOption::parse(vec_data) {
-                    Ok(data) => return Data::RequestOption(data),
-                    Err(_e) => {},
-                }
+                return RequestOption::parse(vec_data).map(Data::RequestOption);
             }
         },
+        // Message = 0x05
+        DataType::Message => {
+            return Message::parse(vec_data).map(Data::Message);
+        },
         // Address = 0x06
         DataType::Address => {
-            match Address::parse(vec_data) {
-                Ok(data) => return Data::Address(data),
-                Err(_e) => {},
-            }
+            return Address::parse(vec_data).map(Data::Address);
         },
         // Information = 0x07
         DataType::Information => {
-            match Information::parse(vec_data) {
-                Ok(data) => return Data::Information(data),
-                Err(_e) => {},
-            }
+            return Information::parse(vec_data).map(Data::Information);
         },
         // Update = 0x08
         DataType::Update => {
-            match Update::parse(vec_data) {
-                Ok(data) => return Data::Update(data),
-                Err(_e) => {},
-            }
+            return Update::parse(vec_data).map(Data::Update);
         },
         // UpdateLocation = 0x09
         DataType::UpdateLocation => {
-            match UpdateLocation::parse(vec_data) {
-                Ok(data) => return Data::UpdateLocation(data),
-                Err(_e) => {},
-            }
+            return UpdateLocation::parse(vec_data).map(Data::UpdateLocation);
+        },
+        // SystemCount = 0x0B
+        DataType::SystemCount => {
+            return SystemCount::parse(vec_data).map(Data::SystemCount);
         },
         // SystemInformation = 0x0C
         DataType::SystemInformation => {
-            match SystemInformation::parse(vec_data) {
-                Ok(data) => return Data::SystemInformation(data),
-                Err(_e) => {},
-            }
+            return SystemInformation::parse(vec_data).map(Data::SystemInformation);
+        },
+        // Registration = 0x0D
+        DataType::Registration => {
+            return Registration::parse(vec_data).map(Data::Registration);
         },
         // Administrator = 0x0E
         DataType::Administrator => {
-            match Administrator::parse(vec_data) {
-                Ok(data) => return Data::Administrator(data),
-                Err(_e) => {},
-            }
+            return Administrator::parse(vec_data).map(Data::Administrator);
         },
 
         // Monitor = 0x0F
         DataType::Monitor => {
-            let monitor_header_type = monitor::HeaderType::from_u8(vec_data[0]);
+            if vec_data.is_empty() {
+                return Err(ProtocolError::Truncated{ expected: 1, actual: 0 });
+            }
+
+            let monitor_header_type: monitor::HeaderType = enum_from_u8("monitor::HeaderType", vec_data[0])?;
             match monitor_header_type {
                 monitor::HeaderType::Monitor0 => {
-                    match monitor::Monitor0::parse(&vec_data[1..]) {
-                        Ok(data) => return Data::Monitor0(data),
-                        Err(_e) => {},
-                    }
+                    return monitor::Monitor0::parse(&vec_data[1..]).map(Data::Monitor0);
                 },
                 monitor::HeaderType::Monitor4 => {
-                    match monitor::Monitor4::parse(&vec_data[1..]) {
-                        Ok(data) => return Data::Monitor4(data),
-                        Err(_e) => {},
-                    }
+                    return monitor::Monitor4::parse(&vec_data[1..]).map(Data::Monitor4);
                 },
                 monitor::HeaderType::Monitor8 => {
-                    match monitor::Monitor8::parse(&vec_data[1..]) {
-                        Ok(data) => return Data::Monitor8(data),
-                        Err(_e) => {},
-                    }
+                    return monitor::Monitor8::parse(&vec_data[1..]).map(Data::Monitor8);
                 },
             }
         },
@@ -117,482 +134,562 @@ pub fn check(header: &Header, vec_data: &Vec<u8>) -> Data
         // Control = 0x10
         DataType::Control => {
             if length == control::WheelAccel8::size() {
-                match control::WheelAccel8::parse(vec_data) {
-                    Ok(data) => return Data::WheelAccel8(data),
-                    Err(_e) => {},
-                }
+                return control::WheelAccel8::parse(vec_data).map(Data::WheelAccel8);
             }
             else if length == control::WheelAccel8AndRequestData::size() {
-                match control::WheelAccel8AndRequestData::parse(vec_data) {
-                    Ok(data) => return Data::WheelAccel8AndRequestData(data),
-                    Err(_e) => {},
-                }
+                return control::WheelAccel8AndRequestData::parse(vec_data).map(Data::WheelAccel8AndRequestData);
             }
             else if length == control::Quad8::size() {
-                match control::Quad8::parse(vec_data) {
-                    Ok(data) => return Data::Quad8(data),
-                    Err(_e) => {},
-                }
+                return control::Quad8::parse(vec_data).map(Data::Quad8);
             }
             else if length == control::Quad8AndRequestData::size() {
-                match control::Quad8AndRequestData::parse(vec_data) {
-                    Ok(data) => return Data::Quad8AndRequestData(data),
-                    Err(_e) => {},
-                }
+                return control::Quad8AndRequestData::parse(vec_data).map(Data::Quad8AndRequestData);
             }
             else if length == control::Position16::size() {
-                match control::Position16::parse(vec_data) {
-                    Ok(data) => return Data::ControlPosition16(data),
-                    Err(_e) => {},
-                }
+                return control::Position16::parse(vec_data).map(Data::ControlPosition16);
             }
             else if length == control::Position::size() {
-                match control::Position::parse(vec_data) {
-                    Ok(data) => return Data::ControlPosition(data),
-                    Err(_e) => {},
-                }
+                return control::Position::parse(vec_data).map(Data::ControlPosition);
             }
         },
 
         // Control = 0x11
         DataType::Command => {
             if length == command::Command::size() {
-                match command::Command::parse(vec_data) {
-                    Ok(data) => return Data::Command(data),
-                    Err(_e) => {},
-                }
+                return command::Command::parse(vec_data).map(Data::Command);
             }
             else if length == command::CommandLightEvent::size() {
-                match command::CommandLightEvent::parse(vec_data) {
-                    Ok(data) => return Data::CommandLightEvent(data),
-                    Err(_e) => {},
-                }
+                return command::CommandLightEvent::parse(vec_data).map(Data::CommandLightEvent);
             }
             else if length == command::CommandLightEventColor::size() {
-                match command::CommandLightEventColor::parse(vec_data) {
-                    Ok(data) => return Data::CommandLightEventColor(data),
-                    Err(_e) => {},
-                }
+                return command::CommandLightEventColor::parse(vec_data).map(Data::CommandLightEventColor);
             }
         },
 
         // Pairing = 0x12
         DataType::Pairing => {
-            match communication::Pairing::parse(vec_data) {
-                Ok(data) => return Data::Pairing(data),
-                Err(_e) => {},
-            }
+            return communication::Pairing::parse(vec_data).map(Data::Pairing);
         },
         // Rssi = 0x13
         DataType::Rssi => {
-            match communication::Rssi::parse(vec_data) {
-                Ok(data) => return Data::Rssi(data),
-                Err(_e) => {},
-            }
+            return communication::Rssi::parse(vec_data).map(Data::Rssi);
+        },
+        // Echo = 0x17
+        DataType::Echo => {
+            return Echo::parse(vec_data).map(Data::Echo);
         },
 
         // Battle = 0x1F
         DataType::Battle => {
             if length == battle::IrMessage::size() {
-                match battle::IrMessage::parse(vec_data) {
-                    Ok(data) => return Data::BattleIrMessage(data),
-                    Err(_e) => {},
-                }
+                return battle::IrMessage::parse(vec_data).map(Data::BattleIrMessage);
             }
             else if length == battle::LightEventCommand::size() {
-                match battle::LightEventCommand::parse(vec_data) {
-                    Ok(data) => return Data::BattleLightEventCommand(data),
-                    Err(_e) => {},
-                }
+                return battle::LightEventCommand::parse(vec_data).map(Data::BattleLightEventCommand);
             }
             else if length == battle::IrMessageLightEventCommand::size() {
-                match battle::IrMessageLightEventCommand::parse(vec_data) {
-                    Ok(data) => return Data::BattleIrMessageLightEventCommand(data),
-                    Err(_e) => {},
-                }
+                return battle::IrMessageLightEventCommand::parse(vec_data).map(Data::BattleIrMessageLightEventCommand);
             }
         },
 
         // LightManual = 0x20
         DataType::LightManual => {
-            match light::Manual::parse(vec_data) {
-                Ok(data) => return Data::LightManual(data),
-                Err(_e) => {},
-            }
+            return light::Manual::parse(vec_data).map(Data::LightManual);
         },
         // LightMode = 0x21
         DataType::LightMode => {
-            match light::Mode::parse(vec_data) {
-                Ok(data) => return Data::LightMode(data),
-                Err(_e) => {},
-            }
+            return light::Mode::parse(vec_data).map(Data::LightMode);
         },
         // LightEvent = 0x22
         DataType::LightEvent => {
-            match light::Event::parse(vec_data) {
-                Ok(data) => return Data::LightEvent(data),
-                Err(_e) => {},
-            }
+            return light::Event::parse(vec_data).map(Data::LightEvent);
         },
 
         // RawMotion = 0x30
         DataType::RawMotion => {
-            match sensor::RawMotion::parse(vec_data) {
-                Ok(data) => return Data::RawMotion(data),
-                Err(_e) => {},
-            }
+            return sensor::RawMotion::parse(vec_data).map(Data::RawMotion);
         },
         // RawFlow = 0x31
         DataType::RawFlow => {
-            match sensor::RawFlow::parse(vec_data) {
-                Ok(data) => return Data::RawFlow(data),
-                Err(_e) => {},
-            }
+            return sensor::RawFlow::parse(vec_data).map(Data::RawFlow);
         },
 
+        // State = 0x40
+        DataType::State => {
+            return State::parse(vec_data).map(Data::State);
+        },
         // Attitude = 0x41
         DataType::Attitude => {
-            match sensor::Attitude::parse(vec_data) {
-                Ok(data) => return Data::Attitude(data),
-                Err(_e) => {},
-            }
+            return sensor::Attitude::parse(vec_data).map(Data::Attitude);
         },
         // Position = 0x42
         DataType::Position => {
             if length == sensor::Position::size() {
-                match sensor::Position::parse(vec_data) {
-                    Ok(data) => return Data::Position(data),
-                    Err(_e) => {},
-                }
+                return sensor::Position::parse(vec_data).map(Data::Position);
             }
             else if length == sensor::PositionVelocity::size() {
-                match sensor::PositionVelocity::parse(vec_data) {
-                    Ok(data) => return Data::PositionVelocity(data),
-                    Err(_e) => {},
-                }
+                return sensor::PositionVelocity::parse(vec_data).map(Data::PositionVelocity);
             }
         },
+        // Altitude = 0x43
+        DataType::Altitude => {
+            return sensor::Altitude::parse(vec_data).map(Data::Altitude);
+        },
         // Motion = 0x44
         DataType::Motion => {
-            match sensor::Motion::parse(vec_data) {
-                Ok(data) => return Data::Motion(data),
-                Err(_e) => {},
-            }
+            return sensor::Motion::parse(vec_data).map(Data::Motion);
         },
         // Range = 0x45
         DataType::Range => {
-            match sensor::Range::parse(vec_data) {
-                Ok(data) => return Data::Range(data),
-                Err(_e) => {},
-            }
+            return sensor::Range::parse(vec_data).map(Data::Range);
         },
         // Flow = 0x46
         DataType::Flow => {
-            match sensor::Flow::parse(vec_data) {
-                Ok(data) => return Data::Flow(data),
-                Err(_e) => {},
-            }
+            return sensor::Flow::parse(vec_data).map(Data::Flow);
         },
 
         // Count = 0x50
         DataType::Count => {
-            match Count::parse(vec_data) {
-                Ok(data) => return Data::Count(data),
-                Err(_e) => {},
-            }
+            return Count::parse(vec_data).map(Data::Count);
         },
         // Bias = 0x51
         DataType::Bias => {
-            match sensor::Bias::parse(vec_data) {
-                Ok(data) => return Data::Bias(data),
-                Err(_e) => {},
-            }
+            return sensor::Bias::parse(vec_data).map(Data::Bias);
         },
         // Trim = 0x52
         DataType::Trim => {
-            match sensor::Trim::parse(vec_data) {
-                Ok(data) => return Data::Trim(data),
-                Err(_e) => {},
-            }
+            return sensor::Trim::parse(vec_data).map(Data::Trim);
+        },
+        // Weight = 0x53
+        DataType::Weight => {
+            return sensor::Weight::parse(vec_data).map(Data::Weight);
         },
         // LostConnection = 0x54
         DataType::LostConnection => {
-            match communication::LostConnection::parse(vec_data) {
-                Ok(data) => return Data::LostConnection(data),
-                Err(_e) => {},
-            }
+            return communication::LostConnection::parse(vec_data).map(Data::LostConnection);
         },
         // MagnetometerOffset = 0x55
         DataType::MagnetometerOffset => {
-            match sensor::MagnetometerOffset::parse(vec_data) {
-                Ok(data) => return Data::MagnetometerOffset(data),
-                Err(_e) => {},
-            }
+            return sensor::MagnetometerOffset::parse(vec_data).map(Data::MagnetometerOffset);
         },
 
         // Motor = 0x60
         DataType::Motor => {
             if length == motor::MotorV::size() {
-                match motor::MotorV::parse(vec_data) {
-                    Ok(data) => return Data::MotorV(data),
-                    Err(_e) => {},
-                }
+                return motor::MotorV::parse(vec_data).map(Data::MotorV);
             }
             else if length == motor::MotorRV::size() {
-                match motor::MotorRV::parse(vec_data) {
-                    Ok(data) => return Data::MotorRV(data),
-                    Err(_e) => {},
-                }
+                return motor::MotorRV::parse(vec_data).map(Data::MotorRV);
             }
             else if length == motor::MotorVA::size() {
-                match motor::MotorVA::parse(vec_data) {
-                    Ok(data) => return Data::MotorVA(data),
-                    Err(_e) => {},
-                }
+                return motor::MotorVA::parse(vec_data).map(Data::MotorVA);
             }
             else if length == motor::MotorRVA::size() {
-                match motor::MotorRVA::parse(vec_data) {
-                    Ok(data) => return Data::MotorRVA(data),
-                    Err(_e) => {},
-                }
+                return motor::MotorRVA::parse(vec_data).map(Data::MotorRVA);
             }
         },
 
         // Motor = 0x61
         DataType::MotorSingle => {
             if length == motor::MotorSingleV::size() {
-                match motor::MotorSingleV::parse(vec_data) {
-                    Ok(data) => return Data::MotorSingleV(data),
-                    Err(_e) => {},
-                }
+                return motor::MotorSingleV::parse(vec_data).map(Data::MotorSingleV);
             }
             else if length == motor::MotorSingleRV::size() {
-                match motor::MotorSingleRV::parse(vec_data) {
-                    Ok(data) => return Data::MotorSingleRV(data),
-                    Err(_e) => {},
-                }
+                return motor::MotorSingleRV::parse(vec_data).map(Data::MotorSingleRV);
             }
         },
 
         // Buzzer = 0x62
         DataType::Buzzer => {
             if length == buzzer::Melody::size() {
-                match buzzer::Melody::parse(vec_data) {
-                    Ok(data) => return Data::Melody(data),
-                    Err(_e) => {},
-                }
+                return buzzer::Melody::parse(vec_data).map(Data::Melody);
             }
-            else if length == buzzer::BuzzerScale::size() {
-                match buzzer::BuzzerScale::parse(vec_data) {
-                    Ok(data) => return Data::BuzzerScale(data),
-                    Err(_e) => {},
-                }
+            // BuzzerScale과 BuzzerHz는 길이가 같으므로 mode로 구분
+            else if length == buzzer::BuzzerHz::size() && matches!(buzzer::Mode::from_u8(vec_data[0]), buzzer::Mode::HzInstantly | buzzer::Mode::HzContinually) {
+                return buzzer::BuzzerHz::parse(vec_data).map(Data::BuzzerHz);
             }
-            else if length == buzzer::BuzzerHz::size() {
-                match buzzer::BuzzerHz::parse(vec_data) {
-                    Ok(data) => return Data::BuzzerHz(data),
-                    Err(_e) => {},
-                }
+            else if length == buzzer::BuzzerScale::size() {
+                return buzzer::BuzzerScale::parse(vec_data).map(Data::BuzzerScale);
             }
         },
 
         // Button = 0x70
         DataType::Button => {
-            match button::Button::parse(vec_data) {
-                Ok(data) => return Data::Button(data),
-                Err(_e) => {},
-            }
+            return button::Button::parse(vec_data).map(Data::Button);
         },
         // Joystick = 0x71
         DataType::Joystick => {
-            match joystick::Joystick::parse(vec_data) {
-                Ok(data) => return Data::Joystick(data),
-                Err(_e) => {},
-            }
+            return joystick::Joystick::parse(vec_data).map(Data::Joystick);
         },
 
         // DisplayClear = 0x80
         DataType::DisplayClear => {
             if length == display::ClearAll::size() {
-                match display::ClearAll::parse(vec_data) {
-                    Ok(data) => return Data::DisplayClearAll(data),
-                    Err(_e) => {},
-                }
+                return display::ClearAll::parse(vec_data).map(Data::DisplayClearAll);
             }
             else if length == display::Clear::size() {
-                match display::Clear::parse(vec_data) {
-                    Ok(data) => return Data::DisplayClear(data),
-                    Err(_e) => {},
-                }
+                return display::Clear::parse(vec_data).map(Data::DisplayClear);
             }
         },
         // Invert = 0x81
         DataType::DisplayInvert => {
-            match display::Invert::parse(vec_data) {
-                Ok(data) => return Data::DisplayInvert(data),
-                Err(_e) => {},
-            }
+            return display::Invert::parse(vec_data).map(Data::DisplayInvert);
         },
         // DrawPoint = 0x82
         DataType::DisplayDrawPoint => {
-            match display::DrawPoint::parse(vec_data) {
-                Ok(data) => return Data::DisplayDrawPoint(data),
-                Err(_e) => {},
-            }
+            return display::DrawPoint::parse(vec_data).map(Data::DisplayDrawPoint);
         },
         // DisplayDrawLine = 0x83
         DataType::DisplayDrawLine => {
-            match display::DrawLine::parse(vec_data) {
-                Ok(data) => return Data::DisplayDrawLine(data),
-                Err(_e) => {},
-            }
+            return display::DrawLine::parse(vec_data).map(Data::DisplayDrawLine);
         },
         // DisplayDrawRect = 0x84
         DataType::DisplayDrawRect => {
-            match display::DrawRect::parse(vec_data) {
-                Ok(data) => return Data::DisplayDrawRect(data),
-                Err(_e) => {},
-            }
+            return display::DrawRect::parse(vec_data).map(Data::DisplayDrawRect);
         },
         // DisplayDrawCircle = 0x85
         DataType::DisplayDrawCircle => {
-            match display::DrawCircle::parse(vec_data) {
-                Ok(data) => return Data::DisplayDrawCircle(data),
-                Err(_e) => {},
-            }
+            return display::DrawCircle::parse(vec_data).map(Data::DisplayDrawCircle);
         },
         // DisplayDrawString = 0x86
         DataType::DisplayDrawString => {
-            match display::DrawString::parse(vec_data) {
-                Ok(data) => return Data::DisplayDrawString(data),
-                Err(_e) => {},
-            }
+            return display::DrawString::parse(vec_data).map(Data::DisplayDrawString);
         },
         // DisplayDrawStringAlign = 0x87
         DataType::DisplayDrawStringAlign => {
-            match display::DrawStringAlign::parse(vec_data) {
-                Ok(data) => return Data::DisplayDrawStringAlign(data),
-                Err(_e) => {},
-            }
+            return display::DrawStringAlign::parse(vec_data).map(Data::DisplayDrawStringAlign);
         },
         // DisplayDrawImage = 0x88
         DataType::DisplayDrawImage => {
-            match display::DrawImage::parse(vec_data) {
-                Ok(data) => return Data::DisplayDrawImage(data),
-                Err(_e) => {},
-            }
+            return display::DrawImage::parse(vec_data).map(Data::DisplayDrawImage);
+        },
+
+
+        // CardClassify = 0x90
+        DataType::CardClassify => {
+            return card::Classify::parse_array(vec_data).map(Data::CardClassify);
+        },
+        // CardRange = 0x91
+        DataType::CardRange => {
+            return card::Range::parse(vec_data).map(Data::CardRange);
+        },
+        // CardRaw = 0x92
+        DataType::CardRaw => {
+            return card::Raw::parse(vec_data).map(Data::CardRaw);
+        },
+        // CardColor = 0x93
+        DataType::CardColor => {
+            return card::Color::parse(vec_data).map(Data::CardColor);
+        },
+        // CardList = 0x94
+        DataType::CardList => {
+            return card::ListCard::parse(vec_data).map(Data::CardListCard);
+        },
+        // CardFunctionList = 0x95
+        DataType::CardFunctionList => {
+            return card::ListFunction::parse(vec_data).map(Data::CardListFunction);
+        },
+
+        // InformationAssembledForController = 0xA0
+        DataType::InformationAssembledForController => {
+            return assembled::ForController::parse(vec_data).map(Data::InformationAssembledForController);
+        },
+        // InformationAssembledForEntry = 0xA1
+        DataType::InformationAssembledForEntry => {
+            return assembled::ForEntry::parse(vec_data).map(Data::InformationAssembledForEntry);
+        },
+        // InformationAssembledForByBlocks = 0xA2
+        DataType::InformationAssembledForByBlocks => {
+            return assembled::ByBlocks::parse_with_profile(profile, vec_data).map(Data::InformationAssembledForByBlocks);
         },
 
 
         // NavigationTarget = 0xD0
         DataType::NavigationTarget => {
             if length == navigation::Target::size() {
-                match navigation::Target::parse(vec_data) {
-                    Ok(data) => return Data::NavigationTarget(data),
-                    Err(_e) => {},
-                }
+                return navigation::Target::parse(vec_data).map(Data::NavigationTarget);
             }
             else if length == navigation::TargetLight::size() {
-                match navigation::TargetLight::parse(vec_data) {
-                    Ok(data) => return Data::NavigationTargetLight(data),
-                    Err(_e) => {},
-                }
+                return navigation::TargetLight::parse(vec_data).map(Data::NavigationTargetLight);
             }
         },
         // NavigationLocation = 0xD1
         DataType::NavigationLocation => {
-            match navigation::Location::parse(vec_data) {
-                Ok(data) => return Data::NavigationLocation(data),
-                Err(_e) => {},
-            }
+            return navigation::Location::parse(vec_data).map(Data::NavigationLocation);
         },
         // NavigationMonitor = 0xD2
         DataType::NavigationMonitor => {
-            match navigation::Monitor::parse(vec_data) {
-                Ok(data) => return Data::NavigationMonitor(data),
-                Err(_e) => {},
-            }
+            return navigation::Monitor::parse(vec_data).map(Data::NavigationMonitor);
         },
         // NavigationHeading = 0xD3
         DataType::NavigationHeading => {
-            match navigation::Heading::parse(vec_data) {
-                Ok(data) => return Data::NavigationHeading(data),
-                Err(_e) => {},
-            }
+            return navigation::Heading::parse(vec_data).map(Data::NavigationHeading);
         },
         // NavigationCounter = 0xD4
         DataType::NavigationCounter => {
-            match navigation::Counter::parse(vec_data) {
-                Ok(data) => return Data::NavigationCounter(data),
-                Err(_e) => {},
-            }
+            return navigation::Counter::parse(vec_data).map(Data::NavigationCounter);
         },
         // NavigationSatellite = 0xD5
         DataType::NavigationSatellite => {
-            match navigation::Satellite::parse(vec_data) {
-                Ok(data) => return Data::NavigationSatellite(data),
-                Err(_e) => {},
-            }
+            return navigation::Satellite::parse(vec_data).map(Data::NavigationSatellite);
         },
         // NavigationLocationAdjust = 0xD6
         DataType::NavigationLocationAdjust => {
-            match navigation::LocationAdjust::parse(vec_data) {
-                Ok(data) => return Data::NavigationLocationAdjust(data),
-                Err(_e) => {},
-            }
+            return navigation::LocationAdjust::parse(vec_data).map(Data::NavigationLocationAdjust);
+        },
+        // NavigationTargetEcef = 0xD8
+        DataType::NavigationTargetEcef => {
+            return navigation::TargetEcef::parse(vec_data).map(Data::NavigationTargetEcef);
+        },
+        // NavigationLocationEcef = 0xD9
+        DataType::NavigationLocationEcef => {
+            return navigation::LocationEcef::parse(vec_data).map(Data::NavigationLocationEcef);
+        },
+        // GpsRtkNavigationState = 0xDA
+        DataType::GpsRtkNavigationState => {
+            return navigation::RtkNavigationState::parse(vec_data).map(Data::GpsRtkNavigationState);
+        },
+        // GpsRtkExtendedRawMeasurementData = 0xDB
+        DataType::GpsRtkExtendedRawMeasurementData => {
+            return navigation::RtkExtendedRawMeasurementData::parse(vec_data).map(Data::GpsRtkExtendedRawMeasurementData);
         },
 
         // UwbPosition = 0xE0
         DataType::UwbPosition => {
-            match external::uwb::Position::parse(vec_data) {
-                Ok(data) => return Data::UwbPosition(data),
-                Err(_e) => {},
-            }
+            return external::uwb::Position::parse(vec_data).map(Data::UwbPosition);
         },
 
         // TagData = 0xE1
         DataType::TagData => {
-            match external::tag::TagData::parse(vec_data) {
-                Ok(data) => return Data::TagData(data),
-                Err(_e) => {},
-            }
+            return external::tag::TagData::parse(vec_data).map(Data::TagData);
         },
 
-        // LidarData = 0xE1
+        // LidarData = 0xE2
         DataType::LidarData => {
-            match external::lidar::LidarData::parse(vec_data) {
-                Ok(data) => return Data::LidarData(data),
-                Err(_e) => {},
-            }
-        },
-        /*
-        // ExternalSystemCommand = 0xE1
-        DataType::ExternalSystemCommand => {
-            match external::system::Command::parse(vec_data) {
-                Ok(data) => return Data::ExternalSystemCommand(data),
-                Err(_e) => {},
-            }
+            return external::lidar::LidarData::parse(vec_data).map(Data::LidarData);
         },
-        // ExternalCameraState = 0xE2
-        DataType::ExternalCameraState => {
-            match external::camera::State::parse(vec_data) {
-                Ok(data) => return Data::ExternalCameraState(data),
-                Err(_e) => {},
-            }
-        },
-        // ExternalCameraCommand = 0xE3
+        // ExternalCameraCommand = 0xE3 (Project P와 겹치지 않으므로 Profile과 관계없이 해석)
         DataType::ExternalCameraCommand => {
-            match external::camera::Command::parse(vec_data) {
-                Ok(data) => return Data::ExternalCameraCommand(data),
-                Err(_e) => {},
-            }
+            return external::camera::Command::parse(vec_data).map(Data::ExternalCameraCommand);
         },
-        // */
 
         _ => {},
     }
 
 
-    Data::ErrorMessage(format!("Handler / Parsing Error / DataType: {:?}, Length: {}, Data: {:?}", header.data_type, header.length, vec_data))
+    Err(ProtocolError::Unsupported{ data_type: header.data_type, length })
 }
 
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::{*};
+    use crate::communication::{frame::Frame, transfer};
+
+    // 전송 가능한 모든 Data
+    fn samples() -> Vec<Data>
+    {
+        let event = light::Event{event: 3, interval: 500, repeat: 4};
+        let color = light::Color{r: 10, g: 20, b: 30};
+        let command = command::Command{command_type: command::CommandType::Headless, option: 1};
+        let joystick_block = joystick::JoystickBlock{x: -50, y: 60, direction: joystick::Direction::VT, event: joystick::Event::In};
+
+        vec![
+            Data::Ping(Ping{system_time: 0x0102030405060708}),
+            Data::Ack(Ack{system_time: 1000, data_type: DataType::Command, crc16: 0xABCD}),
+            Data::Error(Error{system_time: 2000, error_flags_for_sensor: 0x11223344, error_flags_for_state: 0x55667788}),
+            Data::Request(Request{data_type: DataType::Attitude}),
+            Data::RequestOption(RequestOption{data_type: DataType::Motion, option: 0x12345678}),
+            Data::Message(Message{message: String::from("bootloader ok")}),
+            Data::Address(Address{vec_address: (1..=16).collect()}),
+            Data::Information(Information{mode_update: ModeUpdate::Ready, model_number: ModelNumber::Drone4DroneP5, version: Version{build: 300, minor: 2, major: 22}, year: 2022, month: 6, day: 2}),
+            Data::Update(Update{index_block_next: 7, vec_data: (0..16).collect()}),
+            Data::UpdateLocation(UpdateLocation{index_block_next: 8}),
+            Data::SystemCount(SystemCount{time_system: 123456, count_reset: 3, count_error: 1}),
+            Data::SystemInformation(SystemInformation{crc32_bootloader: 0xAABBCCDD, crc32_application: 0x11223344}),
+            Data::Registration(Registration{vec_data: (0..20).collect()}),
+            Data::Administrator(Administrator{vec_key: (100..116).collect()}),
+            Data::Monitor0(monitor::Monitor0{data_type: monitor::DataType::S16, index: 1, vec_value: vec![-300.0, 0.0, 1200.0]}),
+            Data::Monitor4(monitor::Monitor4{system_time: 123456, data_type: monitor::DataType::F32, index: 2, vec_value: vec![1.5, -0.25]}),
+            Data::Monitor8(monitor::Monitor8{system_time: 1 << 40, data_type: monitor::DataType::U8, index: 3, vec_value: vec![1.0, 2.0, 255.0]}),
+
+            Data::WheelAccel8(control::WheelAccel8{wheel: -10, accel: 20}),
+            Data::WheelAccel8AndRequestData(control::WheelAccel8AndRequestData{wheel: 10, accel: -20, data_type: DataType::State}),
+            Data::Quad8(control::Quad8{roll: 1, pitch: -2, yaw: 3, throttle: -4}),
+            Data::Quad8AndRequestData(control::Quad8AndRequestData{roll: -1, pitch: 2, yaw: -3, throttle: 4, data_type: DataType::Attitude}),
+            Data::ControlPosition16(control::Position16{position_x: 1, position_y: -2, position_z: 3, velocity: 4, heading: -5, rotational_velocity: 6}),
+            Data::ControlPosition(control::Position{x: 1.5, y: -2.5, z: 0.75, velocity: 0.5, heading: 90, rotational_velocity: -45}),
+
+            Data::Command(command),
+            Data::CommandLightEvent(command::CommandLightEvent{command, light_event: event}),
+            Data::CommandLightEventColor(command::CommandLightEventColor{command, event, color}),
+
+            Data::Pairing(communication::Pairing{address: [0x1111, 0x2222, 0x3333], scramble: 5, channel: [1, 2, 3, 4]}),
+            Data::Rssi(communication::Rssi{rssi: -60}),
+            Data::Echo(Echo{vec_data: (0..32).collect()}),
+
+            Data::BattleIrMessage(battle::IrMessage{ir_message: 9}),
+            Data::BattleLightEventCommand(battle::LightEventCommand{event, color, command}),
+            Data::BattleIrMessageLightEventCommand(battle::IrMessageLightEventCommand{ir_message: 9, event, color, command}),
+
+            Data::LightManual(light::Manual{flags: 0x0F0F, brightness: 200}),
+            Data::LightMode(light::Mode{mode: 0x22, interval: 1000}),
+            Data::LightEvent(event),
+
+            Data::RawMotion(sensor::RawMotion{accel_x: 1, accel_y: -2, accel_z: 3, gyro_roll: -4, gyro_pitch: 5, gyro_yaw: -6}),
+            Data::RawFlow(sensor::RawFlow{x: 0.25, y: -0.5}),
+
+            Data::State(State{mode_system: ModeSystem::Start, mode_flight: ModeFlight::Ready, mode_control_flight: ModeControlFlight::Attitude, mode_movement: ModeMovement::Hovering, headless: Headless::Normal, control_speed: 2, sensor_orientation: SensorOrientation::Normal, battery: 87}),
+            Data::Attitude(sensor::Attitude{roll: 10, pitch: -20, yaw: 30}),
+            Data::Position(sensor::Position{x: 1.0, y: 2.0, z: -3.0}),
+            Data::PositionVelocity(sensor::PositionVelocity{x: 1.0, y: 2.0, z: 3.0, vx: -0.1, vy: -0.2, vz: -0.3}),
+            Data::Altitude(sensor::Altitude{temperature: 24.5, pressure: 101325.0, altitude: 12.5, range_height: 0.8}),
+            Data::Motion(sensor::Motion{accel_x: 1, accel_y: 2, accel_z: 3, gyro_roll: 4, gyro_pitch: 5, gyro_yaw: 6, angle_roll: 7, angle_pitch: 8, angle_yaw: 9}),
+            Data::Range(sensor::Range{left: 1, front: 2, right: 3, rear: 4, top: 5, bottom: 6}),
+            Data::Flow(sensor::Flow{x: 0.1, y: 0.2, z: 0.3}),
+
+            Data::Count(Count{time_system: 100, time_flight: 50, count_takeoff: 3, count_landing: 2, count_accident: 1}),
+            Data::Bias(sensor::Bias{accel_x: -1, accel_y: -2, accel_z: -3, gyro_roll: 4, gyro_pitch: 5, gyro_yaw: 6}),
+            Data::Trim(sensor::Trim{roll: 1, pitch: -1, yaw: 2, throttle: -2}),
+            Data::Weight(sensor::Weight{weight: 37.5}),
+            Data::LostConnection(communication::LostConnection{time_neutral: 1000, time_landing: 2000, time_stop: 30000}),
+            Data::MagnetometerOffset(sensor::MagnetometerOffset{offset: -15}),
+
+            Data::MotorV(motor::MotorV{value: 1000}),
+            Data::MotorRV(motor::MotorRV{rotation: Rotation::Counterclockwise, value: 2000}),
+            Data::MotorVA(motor::MotorVA{value: 3000, adc: 40}),
+            Data::MotorRVA(motor::MotorRVA{rotation: Rotation::Clockwise, value: 4000, adc: 50}),
+            Data::MotorSingleV(motor::MotorSingleV{target: 2, value: 500}),
+            Data::MotorSingleRV(motor::MotorSingleRV{target: 3, rotation: Rotation::Counterclockwise, value: 600}),
+
+            Data::Melody(buzzer::Melody{melody: 4, repeat: 2}),
+            Data::BuzzerScale(buzzer::BuzzerScale{mode: buzzer::Mode::ScaleInstantly, scale: buzzer::Scale::E2, time: 100}),
+            Data::BuzzerHz(buzzer::BuzzerHz{mode: buzzer::Mode::HzContinually, hz: 440, time: 200}),
+
+            Data::Button(button::Button{button: 0x0010, event: button::Event::Down}),
+            Data::Joystick(joystick::Joystick{left: joystick_block, right: joystick::JoystickBlock{x: 5, ..joystick_block}}),
+
+            Data::DisplayClearAll(display::ClearAll{pixel: display::Pixel::White}),
+            Data::DisplayClear(display::Clear{x: 1, y: 2, width: 30, height: 40, pixel: display::Pixel::Inverse}),
+            Data::DisplayInvert(display::Invert{x: 1, y: 2, width: 30, height: 40}),
+            Data::DisplayDrawPoint(display::DrawPoint{x: 5, y: 6, pixel: display::Pixel::White}),
+            Data::DisplayDrawLine(display::DrawLine{x1: 1, y1: 2, x2: 3, y2: 4, pixel: display::Pixel::White, line: display::Line::Dotted}),
+            Data::DisplayDrawRect(display::DrawRect{x: 1, y: 2, width: 3, height: 4, pixel: display::Pixel::White, fill: true, line: display::Line::Dashed}),
+            Data::DisplayDrawCircle(display::DrawCircle{x: 10, y: 20, radius: 5, pixel: display::Pixel::White, fill: true}),
+            Data::DisplayDrawString(display::DrawString{x: 1, y: 2, font: display::Font::LM10x16, pixel: display::Pixel::White, string: String::from("hello")}),
+            Data::DisplayDrawStringAlign(display::DrawStringAlign{x_start: 0, x_end: 127, y: 10, align: display::Align::Center, font: display::Font::LM5x8, pixel: display::Pixel::White, string: String::from("drone")}),
+            Data::DisplayDrawImage(display::DrawImage{x: 1, y: 2, width: 8, height: 2, vec_image: vec![0xAA, 0x55]}),
+
+            Data::CardClassify(vec![card::Classify{index: 1, cc: (0..36).collect(), l: vec![-1, 1]}, card::Classify{index: 2, cc: (-36..0).collect(), l: vec![3, -3]}]),
+            Data::CardRange(card::Range{range: (-6..6).collect()}),
+            Data::CardRaw(card::Raw{raw: (0..6).collect(), rgb: (10..16).collect(), hsvl: (-4..4).collect(), color: vec![2, 3], card: 0x22}),
+            Data::CardColor(card::Color{hsvl: (-4..4).collect(), color: vec![4, 5], card: 0x44}),
+            Data::CardListCard(card::ListCard{index_run: 1, total_size: 5, index: 0, card: vec![0x12, 0x21, 0x41, 0x42, 0x22]}),
+            Data::CardListFunction(card::ListFunction{index_run: 2, total_size: 2, index: 0, card: vec![0x23, 0x24]}),
+
+            Data::InformationAssembledForController(assembled::ForController{angle_roll: -5, angle_pitch: 3, angle_yaw: -170, rpm: 12000, position_x: 120, position_y: -40, position_z: 95, speed_x: 4, speed_y: -2, range_height: 93, rssi: -55}),
+            Data::InformationAssembledForEntry(assembled::ForEntry{angle_roll: 4, angle_pitch: -6, angle_yaw: 90, position_x: -10, position_y: 20, position_z: 80, range_height: 812, altitude: 45.5}),
+            Data::InformationAssembledForByBlocks(assembled::ByBlocks{vec_data: vec![
+                Data::Attitude(sensor::Attitude{roll: 1, pitch: 2, yaw: 3}),
+                Data::Message(Message{message: String::from("block")}),
+                Data::Rssi(communication::Rssi{rssi: -70}),
+            ]}),
+
+            Data::NavigationTarget(navigation::Target{index: 1, mode_action: navigation::mode::Action::Wait, mode_option: navigation::mode::Option::TakePhoto, time: 1000, latitude: 37.5, longitude: 127.0, altitude: 10.0, speed: 1.5, heading: 90.0, rotational_speed: 30.0}),
+            Data::NavigationTargetLight(navigation::TargetLight{index: 2, mode_action: navigation::mode::Action::Wait, mode_option: navigation::mode::Option::VideoCapture, time: 10, latitude: 375000000, longitude: 1270000000, altitude: 1000, speed: 150, heading: 900, rotational_speed: 300}),
+            Data::NavigationLocation(navigation::Location{fix_type: 3, num_sv: 12, latitude: 37.5, longitude: 127.0, altitude: 50.0}),
+            Data::NavigationMonitor(navigation::Monitor{mode_navigation: navigation::mode::Navigation::Ready, distance_to_target: 12.5, velocity: 1.5, heading: 45.0, time_remain: 3000}),
+            Data::NavigationHeading(navigation::Heading{heading: 10.0, heading_path: 20.0, heading_to_target: 30.0, heading_error: -5.0}),
+            Data::NavigationCounter(navigation::Counter{count_per_sec_receive: 50, count_per_sec_transfer: 20}),
+            Data::NavigationSatellite(navigation::Satellite{i_tow: 123456, year: 2022, month: 6, day: 2, hour: 12, min: 30, sec: 45, valid: 7, flags: 1, flags2: 2, g_speed: -100, p_dop: 150}),
+            Data::NavigationLocationAdjust(navigation::LocationAdjust{mode_navigation: 1, latitude: 37.5, longitude: 127.0}),
+            Data::NavigationTargetEcef(navigation::TargetEcef{index: 3, mode_action: navigation::mode::Action::Move, mode_option: navigation::mode::Option::None, time: 2000, location: navigation::LocationXYZ{x: -3052000.5, y: 4040000.25, z: 3863000.0}, speed: 2.0, heading: 180.0, rotational_speed: 45.0}),
+            Data::NavigationLocationEcef(navigation::LocationEcef{fix_type: 3, num_sv: 14, location: navigation::LocationXYZ{x: -3052001.5, y: 4040001.25, z: 3863001.0}}),
+            Data::GpsRtkNavigationState(navigation::RtkNavigationState{version: 1, ref_station_id: 7, i_tow: 345600000, rel_pos_n: 120, rel_pos_e: -35, rel_pos_d: 4, rel_pos_length: 125, rel_pos_heading: 34_000_000, rel_pos_hp_n: 12, rel_pos_hp_e: -5, rel_pos_hp_d: 0, rel_pos_hp_length: 9, acc_n: 14, acc_e: 15, acc_d: 30, acc_length: 20, acc_heading: 150_000, flags: 0x0117, ..navigation::RtkNavigationState::new()}),
+            Data::GpsRtkExtendedRawMeasurementData(navigation::RtkExtendedRawMeasurementData{rcv_tow: 345600.5, week: 2214, leap_s: 18, num_meas: 2, rec_stat: 1, version: 1, reserved: [0, 0], vec_measurement: vec![
+                navigation::RtkRawMeasurement{pr_mes: 20000000.5, cp_mes: 105000000.25, do_mes: -1200.5, gnss_id: 0, sv_id: 12, sig_id: 0, freq_id: 0, locktime: 64500, cno: 42, pr_stdev: 3, cp_stdev: 2, do_stdev: 5, trk_stat: 0x0F, reserved: 0},
+                navigation::RtkRawMeasurement{pr_mes: 21000000.5, cp_mes: 110000000.25, do_mes: 800.0, gnss_id: 2, sv_id: 7, sig_id: 0, freq_id: 0, locktime: 30000, cno: 38, pr_stdev: 4, cp_stdev: 3, do_stdev: 6, trk_stat: 0x07, reserved: 0},
+            ]}),
+
+            Data::UwbPosition(external::uwb::Position{x: 1.0, y: 2.0, z: 3.0, system_time: 1000, anchor_group: 2, error: -1}),
+            Data::TagData(vec![external::tag::TagData{x: 1, y: 2, width: 3, height: 4, id: 5}, external::tag::TagData{x: 6, y: 7, width: 8, height: 9, id: 10}]),
+            Data::LidarData(vec![external::lidar::LidarData{angle_radian_x1000: 1571, distance_mm: 300}]),
+            Data::ExternalCameraCommand(external::camera::Command{command_type: external::camera::CommandType::TakePhoto}),
+        ]
+    }
+
+
+    #[test]
+    fn round_trip() {
+        for data in samples() {
+            let frame = data.to_frame(DeviceType::Drone, DeviceType::Base).unwrap();
+            assert_eq!(frame.header.data_type, data.data_type());
+            assert_eq!(check(&frame.header, &frame.payload).as_ref(), Ok(&data), "{}", frame);
+        }
+
+        assert_eq!(Data::ErrorMessage(String::from("error")).to_frame(DeviceType::Drone, DeviceType::Base), None);
+
+        // 데이터 모음은 e_drone Python 라이브러리와 같은 크기
+        assert_eq!(assembled::ForController::size(), 16);
+        assert_eq!(assembled::ForEntry::size(), 18);
+
+        // 블럭 길이를 넘는 데이터는 데이터 모음에 넣지 않음
+        let by_blocks = assembled::ByBlocks{vec_data: vec![Data::Echo(Echo{vec_data: vec![0; 300]}), Data::Rssi(communication::Rssi{rssi: -70})]};
+        assert_eq!(by_blocks.to_vec(), vec![DataType::Rssi.into(), 1, -70_i8 as u8]);
+
+        // Classify 하나는 Payload로 전송
+        let classify = card::Classify{index: 3, cc: (0..36).collect(), l: vec![1, 2]};
+        let frame = Frame::decode(&transfer::build(DeviceType::Base, DeviceType::Drone, &classify)).unwrap();
+        assert_eq!(frame.to_data(), Ok(Data::CardClassify(vec![classify])));
+
+        // Monitor 값 배열은 data_type 크기의 배수
+        let header = Header{data_type: DataType::Monitor, length: 6, from: DeviceType::Drone, to: DeviceType::Base};
+        assert_eq!(check(&header, &[0x00, 0x03, 0x01, 0x01, 0x02, 0x03]), Err(ProtocolError::LengthMismatch{ expected: 6, actual: 5 }));
+    }
+
+
+    #[test]
+    fn unknown_value() {
+        let header = Header{data_type: DataType::Command, length: 2, from: DeviceType::Base, to: DeviceType::Drone};
+        assert_eq!(check(&header, &[0xEE, 0x01]), Err(ProtocolError::UnknownValue{ field: "Command::command_type", value: 0xEE }));
+
+        let header = Header{data_type: DataType::Request, length: 1, from: DeviceType::Base, to: DeviceType::Drone};
+        assert_eq!(check(&header, &[0xFE]), Err(ProtocolError::UnknownValue{ field: "Request::data_type", value: 0xFE }));
+    }
+
+
+    #[test]
+    fn profile() {
+        let vec_external = vec![
+            Data::ExternalSystemState(external::system::State{mode_system: external::system::ModeSystem::Run, fps: 30}),
+            Data::ExternalSystemCommand(external::system::Command{command_type: external::system::CommandType::Reboot}),
+            Data::ExternalCameraState(external::camera::State{mode_camera: external::camera::ModeCamera::Recording, fps: 24}),
+            Data::ExternalCameraCommand(external::camera::Command{command_type: external::camera::CommandType::VideoRecordingStart}),
+        ];
+
+        for data in vec_external {
+            let frame = data.to_frame(DeviceType::Base, DeviceType::Drone).unwrap();
+            assert_eq!(check_with_profile(Profile::External, &frame.header, &frame.payload).as_ref(), Ok(&data), "{}", frame);
+        }
+
+        // 같은 0xE0을 Profile에 따라 다르게 해석
+        let uwb = Data::UwbPosition(external::uwb::Position{x: 1.0, y: 2.0, z: 3.0, system_time: 1000, anchor_group: 2, error: -1});
+        let frame = uwb.to_frame(DeviceType::Drone, DeviceType::Base).unwrap();
+        assert_eq!(frame.header.data_type, DataType::ExternalSystemState);
+        assert_eq!(frame.to_data(), Ok(uwb));
+        assert!(frame.to_data_with_profile(Profile::External).is_err());
+
+        // 데이터 모음 안의 블럭도 같은 Profile로 해석
+        let state = Data::ExternalSystemState(external::system::State{mode_system: external::system::ModeSystem::Run, fps: 30});
+        let by_blocks = Data::InformationAssembledForByBlocks(assembled::ByBlocks{vec_data: vec![state]});
+        let frame = by_blocks.to_frame(DeviceType::Drone, DeviceType::Base).unwrap();
+        assert_eq!(frame.to_data_with_profile(Profile::External), Ok(by_blocks));
+        assert!(frame.to_data().is_err());
+
+        assert_eq!(Profile::from_model_number(ModelNumber::Drone9DroneP3), Profile::External);
+        assert_eq!(Profile::from_model_number(ModelNumber::Drone4DroneP6), Profile::Uwb);
+    }
+}
@@ -1,7 +1,15 @@
+pub mod clock;
 pub mod crc16;
 pub mod extractor;
+pub mod frame;
 pub mod messaging;
 pub mod receiver;
 pub mod handler;
 pub mod transfer;
+pub mod transport;
+pub mod update;
+#[cfg(feature = "serial")]
+pub mod serial;
+#[cfg(feature = "codec")]
+pub mod codec;
 
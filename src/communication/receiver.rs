@@ -1,15 +1,58 @@
-use std::time::{SystemTime};
+use std::time::Duration;
 use std::collections::VecDeque;
 use std::convert::TryFrom;
+use std::sync::Arc;
 
 use crate::protocol;
-use crate::protocol::Serializable;
 use crate::protocol::DataType;
 use crate::system::DeviceType;
 use crate::communication::crc16;
+use crate::communication::frame::Frame;
+use crate::communication::clock::{Clock, SystemClock};
 use crate::communication::messaging::{Section, State};
 
 
+// -- Statistics ----------------------------------------------------------------------------------------------
+// 수신 상태 통계 (링크 품질 확인용)
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Statistics {
+    pub count_decoded: u64,             // 수신 완료한 데이터 수
+    pub count_crc_failure: u64,         // CRC16 불일치
+    pub count_unknown_data_type: u64,   // 정의되지 않은 DataType
+    pub count_unknown_device_type: u64, // 정의되지 않은 DeviceType
+    pub count_timeout: u64,             // 수신 중 1200ms 이상 데이터가 끊긴 경우
+    pub count_discarded: u64,           // 버린 바이트 수
+    pub time_elapsed: Duration,         // 통계 수집 시간
+}
+
+
+impl Statistics {
+    // 초당 발생 횟수
+    pub fn rate(&self, count: u64) -> f64
+    {
+        let time_elapsed = self.time_elapsed.as_secs_f64();
+
+        if time_elapsed > 0.0 { count as f64 / time_elapsed } else { 0.0 }
+    }
+
+
+    // 이전 통계 이후의 변화량 (최근 구간의 비율을 구할 때 사용)
+    pub fn since(&self, statistics_old: &Statistics) -> Statistics
+    {
+        Statistics{
+            count_decoded: self.count_decoded.saturating_sub(statistics_old.count_decoded),
+            count_crc_failure: self.count_crc_failure.saturating_sub(statistics_old.count_crc_failure),
+            count_unknown_data_type: self.count_unknown_data_type.saturating_sub(statistics_old.count_unknown_data_type),
+            count_unknown_device_type: self.count_unknown_device_type.saturating_sub(statistics_old.count_unknown_device_type),
+            count_timeout: self.count_timeout.saturating_sub(statistics_old.count_timeout),
+            count_discarded: self.count_discarded.saturating_sub(statistics_old.count_discarded),
+            time_elapsed: self.time_elapsed.saturating_sub(statistics_old.time_elapsed),
+        }
+    }
+}
+
+
+// -- Receiver ----------------------------------------------------------------------------------------------
 #[derive(Debug)]
 pub struct Receiver {
     state: State,
@@ -18,25 +61,40 @@ pub struct Receiver {
     index: i32,
 
     header: protocol::Header,
+    header_raw: [u8; 4],    // 받은 헤더 (정의되지 않은 값도 그대로 보관)
 
-    time_receive_start: SystemTime,
-    time_receive_complete: SystemTime,
+    clock: Arc<dyn Clock>,
+    time_receive_start: Duration,
+    time_receive_complete: Duration,
 
     crc16_calculated: u16,
     crc16: u16,
 
     queue_buffer: VecDeque<u8>,
+    vec_frame: Vec<u8>,     // 현재 수신 중인 데이터 (시작 코드부터)
     vec_data: Vec<u8>,
     vec_data_all: Vec<u8>,
 
     flag_connected: bool,
     pub flag_show_debug_message: bool,  // 디버깅 정보 표시
+    pub flag_lenient: bool,             // 정의되지 않은 DataType, DeviceType도 수신
+
+    statistics: Statistics,
+    time_statistics_start: Duration,
 }
 
 
 impl Receiver {
     pub fn new() -> Receiver
     {
+        Receiver::with_clock(Arc::new(SystemClock::new()))
+    }
+
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Receiver
+    {
+        let time_now = clock.now();
+
         Receiver{
             state: State::Ready,
             section_old: Section::End,
@@ -49,19 +107,26 @@ impl Receiver {
                 from: DeviceType::Base,
                 to: DeviceType::Drone,
             },
+            header_raw: [0; 4],
         
-            time_receive_start: SystemTime::now(),
-            time_receive_complete: SystemTime::now(),
+            clock,
+            time_receive_start: time_now,
+            time_receive_complete: time_now,
         
             crc16_calculated: 0,
             crc16: 0,
 
             queue_buffer: VecDeque::with_capacity(4096),
+            vec_frame: Vec::new(),
             vec_data: Vec::new(),
             vec_data_all: Vec::new(),
         
             flag_connected: false,
             flag_show_debug_message: false,
+            flag_lenient: false,
+
+            statistics: Statistics::default(),
+            time_statistics_start: time_now,
         }
     }
 
@@ -72,6 +137,38 @@ impl Receiver {
     }
 
 
+    // 새 펌웨어에서 추가된 데이터처럼 정의되지 않은 DataType, DeviceType을 사용한 데이터도 CRC16이 맞으면 수신
+    // 정의되지 않은 값은 헤더에 None으로 표시되며 원래 값은 get_header_raw()로 확인
+    // 해석은 handler::check_raw를 사용하면 Data::Unknown으로 받을 수 있음
+    pub fn set_lenient(&mut self, flag_lenient: bool)
+    {
+        self.flag_lenient = flag_lenient;
+    }
+
+
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>)
+    {
+        self.clock = clock;
+
+        self.time_receive_start = self.clock.now();
+        self.time_receive_complete = self.time_receive_start;
+        self.time_statistics_start = self.time_receive_start;
+    }
+
+
+    pub fn get_clock(&self) -> &Arc<dyn Clock>
+    {
+        &self.clock
+    }
+
+
+    // 마지막으로 데이터 수신을 시작한 시점 이후 경과 시간
+    fn get_time_passed_from_receive_start(&self) -> Duration
+    {
+        self.clock.now().saturating_sub(self.time_receive_start)
+    }
+
+
     pub fn is_buffer_cleared(&mut self) -> bool
     {
         self.queue_buffer.len() == 0
@@ -101,14 +198,16 @@ impl Receiver {
         self.header.length = 0;
         self.header.from = DeviceType::Base;
         self.header.to = DeviceType::Drone;
+        self.header_raw = [0; 4];
     
-        self.time_receive_start = SystemTime::now();
-        self.time_receive_complete = SystemTime::now();
+        self.time_receive_start = self.clock.now();
+        self.time_receive_complete = self.time_receive_start;
     
         self.crc16_calculated = 0;
         self.crc16 = 0;
 
         self.queue_buffer.clear();
+        self.vec_frame.clear();
         self.vec_data.clear();
         self.vec_data_all.clear();
     
@@ -127,13 +226,8 @@ impl Receiver {
 
 
     pub fn check(&mut self) -> &State {
-        match self.time_receive_start.elapsed() {
-            Ok(elapsed) => {
-                if elapsed.as_millis() > 1200 {
-                    self.flag_connected = false;
-                };
-            },
-            Err(_e) => {},
+        if self.get_time_passed_from_receive_start().as_millis() > 1200 {
+            self.flag_connected = false;
         }
 
         if let State::Loaded = self.state {
@@ -170,19 +264,17 @@ impl Receiver {
             State::Ready => {
                 self.section = Section::Start;
                 self.index = 0;
+                self.vec_frame.clear();
             },
 
-            State::Receiving => {
-                match self.time_receive_start.elapsed() {
-                    Ok(elapsed) => {
-                        if elapsed.as_millis() > 1200 {
-                            self.state = State::Ready;
-                            self.section = Section::Start;
-                            self.index = 0;
-                        };
-                    },
-                    Err(_e) => {},
-                }
+            State::Receiving if self.get_time_passed_from_receive_start().as_millis() > 1200 => {
+                self.state = State::Ready;
+                self.section = Section::Start;
+                self.index = 0;
+
+                self.statistics.count_timeout += 1;
+                self.statistics.count_discarded += self.vec_frame.len() as u64;
+                self.vec_frame.clear();
             },
 
             State::Loaded => {
@@ -198,6 +290,8 @@ impl Receiver {
             self.section_old = self.section;
         }
 
+        self.vec_frame.push(b);
+
 
         match self.section {
             Section::Start =>
@@ -205,7 +299,7 @@ impl Receiver {
                 match self.index {
                     0 => {
                         if b == 0x0A {
-                            self.time_receive_start = SystemTime::now();
+                            self.time_receive_start = self.clock.now();
                             self.state = State::Receiving;
                         }
                         else
@@ -230,11 +324,15 @@ impl Receiver {
 
             Section::Header =>
             {
+                if (self.index as usize) < self.header_raw.len() {
+                    self.header_raw[self.index as usize] = b;
+                }
+
                 match self.index {
                     0 => {
-                        // DataType
+                        // DataType (정의되지 않은 값은 DataType::None으로 변환됨)
                         match DataType::try_from(b){
-                            Ok(data_type) => {
+                            Ok(data_type) if data_type != DataType::None => {
                                 self.header.data_type = data_type;
                                 self.crc16_calculated = crc16::calc_byte(0, b);
                             },
@@ -244,7 +342,15 @@ impl Receiver {
                                     println!("DataType is NOT Matched. - {:X?}", b);
                                 } 
 
-                                self.state = State::Failure;
+                                self.statistics.count_unknown_data_type += 1;
+
+                                if self.flag_lenient {
+                                    self.header.data_type = DataType::None;
+                                    self.crc16_calculated = crc16::calc_byte(0, b);
+                                }
+                                else {
+                                    self.state = State::Failure;
+                                }
                             },
                         }
                     },
@@ -255,25 +361,20 @@ impl Receiver {
                     },
                     2 => {
                         // From
-                        match DeviceType::try_from(b){
-                            Ok(device_type) => {
+                        match self.get_device_type(b) {
+                            Some(device_type) => {
                                 self.header.from = device_type;
                                 self.crc16_calculated = crc16::calc_byte(self.crc16_calculated, b);
                             },
-                            _ => { 
-                                if self.flag_show_debug_message 
-                                {
-                                    println!("DeviceType is NOT Matched. - {:X?}", b);
-                                }
-
+                            None => { 
                                 self.state = State::Failure;
                             },
                         }
                     },
                     3 => {
                         // To
-                        match DeviceType::try_from(b){
-                            Ok(device_type) => {
+                        match self.get_device_type(b) {
+                            Some(device_type) => {
                                 self.header.to = device_type;
                                 self.crc16_calculated = crc16::calc_byte(self.crc16_calculated, b);
                                 self.vec_data.clear();
@@ -286,12 +387,7 @@ impl Receiver {
                                     self.section = Section::Data;
                                 }
                             },
-                            _ => { 
-                                if self.flag_show_debug_message 
-                                {
-                                    println!("DeviceType is NOT Matched. - {:X?}", b);
-                                }
-
+                            None => { 
                                 self.state = State::Failure;
                             },
                         }
@@ -327,18 +423,20 @@ impl Receiver {
                         }
             
                         if self.crc16 == self.crc16_calculated {
-                            self.time_receive_complete = SystemTime::now();
+                            self.time_receive_complete = self.clock.now();
                             self.state = State::Loaded;
+                            self.statistics.count_decoded += 1;
 
                             self.vec_data_all.clear();
                             self.vec_data_all.push(0x0A);
                             self.vec_data_all.push(0x55);
-                            self.vec_data_all.extend_from_slice(self.header.to_vec().as_slice());
+                            self.vec_data_all.extend_from_slice(&self.header_raw);
                             self.vec_data_all.extend_from_slice(self.vec_data.as_slice());
                             self.vec_data_all.push((self.crc16 & 0xff) as u8);
                             self.vec_data_all.push(((self.crc16 >> 8) & 0xff) as u8);
                         }
                         else {
+                            self.statistics.count_crc_failure += 1;
                             self.state = State::Failure;
                         }
                     },
@@ -352,7 +450,10 @@ impl Receiver {
 
         match self.state{
             State::Receiving => { self.index = self.index + 1; },
-            State::Failure => { self.state = State::Ready; },
+            State::Failure => {
+                self.state = State::Ready;
+                self.resync();
+            },
             _ => {}
         }
 
@@ -361,16 +462,84 @@ impl Receiver {
     }
 
 
+    // 정의되지 않은 DeviceType은 lenient 모드일 때만 DeviceType::None으로 받음
+    fn get_device_type(&mut self, b: u8) -> Option<DeviceType>
+    {
+        match DeviceType::try_from(b){
+            Ok(device_type) => Some(device_type),
+            _ => {
+                if self.flag_show_debug_message 
+                {
+                    println!("DeviceType is NOT Matched. - {:X?}", b);
+                }
+
+                self.statistics.count_unknown_device_type += 1;
+
+                if self.flag_lenient { Some(DeviceType::None) } else { None }
+            },
+        }
+    }
+
+
+    // 실패한 데이터의 시작 코드 이후 바이트들을 다시 처리하여
+    // 그 안에 있는 다음 시작 코드(0x0A 0x55)부터 수신을 재시도
+    fn resync(&mut self)
+    {
+        if !self.vec_frame.is_empty() {
+            self.statistics.count_discarded += 1;
+        }
+
+        if self.vec_frame.len() > 1 {
+            for b in self.vec_frame.drain(1..).rev() {
+                self.queue_buffer.push_front(b);
+            }
+        }
+
+        self.vec_frame.clear();
+    }
+
+
+    pub fn get_statistics(&self) -> Statistics
+    {
+        let mut statistics = self.statistics;
+        statistics.time_elapsed = self.clock.now().saturating_sub(self.time_statistics_start);
+
+        statistics
+    }
+
+
+    pub fn reset_statistics(&mut self)
+    {
+        self.statistics = Statistics::default();
+        self.time_statistics_start = self.clock.now();
+    }
+
+
     pub fn get_header(&self) -> &protocol::Header {
         &self.header
     }
 
 
+    pub fn get_header_raw(&self) -> &[u8; 4] {
+        &self.header_raw
+    }
+
+
     pub fn get_data(&self) -> &Vec<u8> {
         &self.vec_data
     }
 
 
+    pub fn get_frame(&self) -> Frame {
+        Frame{
+            header: self.header,
+            header_raw: self.header_raw,
+            payload: self.vec_data.clone(),
+            crc: self.crc16,
+        }
+    }
+
+
     pub fn get_data_all(&self) -> &Vec<u8> {
         &self.vec_data_all
     }
@@ -391,3 +560,162 @@ impl Receiver {
     }
 }
 
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::communication::clock::ManualClock;
+    use crate::communication::{handler, transfer};
+    use crate::protocol::{sensor, Serializable};
+
+    fn receive_all(slice_data: &[u8]) -> Vec<(protocol::Header, Vec<u8>)>
+    {
+        let mut receiver = Receiver::new();
+        receiver.push_slice(slice_data);
+
+        let mut vec_received = Vec::new();
+        while let State::Loaded = receiver.check() {
+            receiver.clear();
+            vec_received.push((*receiver.get_header(), receiver.get_data().clone()));
+        }
+
+        vec_received
+    }
+
+    fn attitude(roll: i16) -> Vec<u8>
+    {
+        transfer::transfer(DataType::Attitude, DeviceType::Drone, DeviceType::Base, &sensor::Attitude{roll, pitch: 0, yaw: 0}.to_vec())
+    }
+
+    #[test]
+    fn resync_crc_mismatch() {
+        // 다른 데이터 안에 들어있는 시작 코드를 찾아야 함
+        let mut vec_outer = transfer::transfer(DataType::Echo, DeviceType::Drone, DeviceType::Base, &attitude(1));
+        let length = vec_outer.len();
+        vec_outer[length - 1] ^= 0xFF;
+        vec_outer.extend(attitude(2));
+
+        let vec_received = receive_all(&vec_outer);
+        assert_eq!(vec_received.len(), 2);
+        assert_eq!(vec_received[0].1, attitude(1)[6..12].to_vec());
+        assert_eq!(vec_received[1].1, attitude(2)[6..12].to_vec());
+    }
+
+    #[test]
+    fn resync_wrong_header() {
+        let mut vec_data = vec![0x00, 0x0A, 0x0A];
+        vec_data.extend(&attitude(1)[1..]);                     // 0x0A 0x0A 0x55 ...
+        vec_data.extend([0x0A, 0x55, 0xFF]);                     // 정의되지 않은 DataType
+        vec_data.extend(attitude(2));
+        vec_data.extend([0x0A, 0x55, 0x41, 0x06, 0x10, 0x55]);   // 정의되지 않은 DeviceType
+        vec_data.extend(attitude(3));
+
+        let vec_received = receive_all(&vec_data);
+        let vec_roll: Vec<u8> = vec_received.iter().map(|(_, vec_data)| vec_data[0]).collect();
+        assert_eq!(vec_roll, vec![1, 2, 3]);
+        assert!(vec_received.iter().all(|(header, _)| header.data_type == DataType::Attitude));
+    }
+
+    #[test]
+    fn timeout() {
+        let clock = Arc::new(ManualClock::new());
+        let mut receiver = Receiver::with_clock(clock.clone());
+
+        // 수신 도중 1200ms 이상 끊긴 데이터는 버림
+        let vec_data = attitude(1);
+        receiver.push_slice(&vec_data[..5]);
+        assert_eq!(*receiver.check(), State::Receiving);
+
+        clock.advance(Duration::from_millis(1201));
+        receiver.push_slice(&vec_data[5..]);
+        receiver.push_slice(&attitude(2));
+        assert_eq!(*receiver.check(), State::Loaded);
+        assert_eq!(receiver.get_data()[0], 2);
+        assert!(*receiver.is_connected());
+
+        let statistics = receiver.get_statistics();
+        assert_eq!(statistics.count_timeout, 1);
+        assert_eq!(statistics.time_elapsed, Duration::from_millis(1201));
+
+        // 마지막 수신 시작 후 1200ms가 지나면 연결 끊김
+        receiver.clear();
+        clock.advance(Duration::from_millis(1200));
+        receiver.check();
+        assert!(*receiver.is_connected());
+
+        clock.advance(Duration::from_millis(1));
+        receiver.check();
+        assert!(!*receiver.is_connected());
+
+        // 이전 시각으로 되돌려도 시간이 거꾸로 가지 않음
+        clock.set(Duration::ZERO);
+        assert_eq!(clock.now(), Duration::from_millis(2402));
+    }
+
+    #[test]
+    fn statistics() {
+        let mut vec_data = vec![0x00];
+        vec_data.extend([0x0A, 0x55, 0xFF]);                     // 정의되지 않은 DataType
+        vec_data.extend([0x0A, 0x55, 0x41, 0x06, 0x10, 0x55]);   // 정의되지 않은 DeviceType
+        let mut vec_crc = attitude(1);
+        vec_crc[6] ^= 0x01;                                      // CRC16 불일치
+        vec_data.extend(&vec_crc);
+        vec_data.extend(attitude(2));
+
+        let mut receiver = Receiver::new();
+        receiver.push_slice(&vec_data);
+        while let State::Loaded = receiver.check() {
+            receiver.clear();
+        }
+
+        let statistics = receiver.get_statistics();
+        assert_eq!(statistics.count_decoded, 1);
+        assert_eq!(statistics.count_crc_failure, 1);
+        assert_eq!(statistics.count_unknown_data_type, 1);
+        assert_eq!(statistics.count_unknown_device_type, 1);
+        assert_eq!(statistics.count_timeout, 0);
+        assert_eq!(statistics.count_discarded, (vec_data.len() - attitude(2).len()) as u64);
+
+        let statistics_recent = statistics.since(&statistics);
+        assert_eq!(statistics_recent.count_decoded, 0);
+        assert_eq!(statistics_recent.rate(statistics_recent.count_decoded), 0.0);
+    }
+
+    #[test]
+    fn lenient() {
+        // CRC16은 맞지만 정의되지 않은 DataType(0xFF)과 DeviceType(0x55)을 사용한 데이터
+        let vec_unknown = transfer::transfer_raw(&[0xFF, 0x03, 0x10, 0x55], &[1, 2, 3]);
+        let mut vec_data = vec_unknown.clone();
+        vec_data.extend(attitude(1));
+
+        assert_eq!(receive_all(&vec_data).len(), 1);
+
+        let mut receiver = Receiver::new();
+        receiver.set_lenient(true);
+        receiver.push_slice(&vec_data);
+
+        assert_eq!(*receiver.check(), State::Loaded);
+        assert_eq!(receiver.get_header().data_type, DataType::None);
+        assert_eq!(receiver.get_header().to, DeviceType::None);
+        assert_eq!(receiver.get_header_raw(), &[0xFF, 0x03, 0x10, 0x55]);
+        assert_eq!(receiver.get_data_all(), &vec_unknown);
+        assert_eq!(handler::check_raw(protocol::Profile::default(), receiver.get_header_raw(), receiver.get_data()),
+                    Ok(protocol::Data::Unknown{ header_raw: [0xFF, 0x03, 0x10, 0x55], payload: vec![1, 2, 3] }));
+
+        let frame = receiver.get_frame();
+        assert!(frame.is_valid());
+        assert_eq!(frame.encode(), vec_unknown);
+        assert_eq!(frame.to_data(), Ok(protocol::Data::Unknown{ header_raw: [0xFF, 0x03, 0x10, 0x55], payload: vec![1, 2, 3] }));
+
+        receiver.clear();
+        assert_eq!(*receiver.check(), State::Loaded);
+        assert_eq!(handler::check_raw(protocol::Profile::default(), receiver.get_header_raw(), receiver.get_data()),
+                    Ok(protocol::Data::Attitude(sensor::Attitude{roll: 1, pitch: 0, yaw: 0})));
+
+        let statistics = receiver.get_statistics();
+        assert_eq!(statistics.count_decoded, 2);
+        assert_eq!(statistics.count_unknown_data_type, 1);
+        assert_eq!(statistics.count_unknown_device_type, 1);
+    }
+}
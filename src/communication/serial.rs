@@ -0,0 +1,123 @@
+use std::io;
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use serialport::{DataBits, FlowControl, Parity, SerialPort, StopBits};
+
+use crate::communication::transport::Transport;
+
+
+// -- Serial ----------------------------------------------------------------------------------------------
+// 조종기 / 드론의 USB 시리얼 포트 (57600bps, 8N1, 흐름 제어 없음)
+pub struct Serial {
+    pub path: String,
+    port: Box<dyn SerialPort>,
+}
+
+
+impl Serial {
+    pub const BAUD_RATE: u32 = 57600;
+    pub const TIMEOUT_MS: u64 = 10;
+
+
+    pub fn open(path: &str) -> io::Result<Serial>
+    {
+        Ok(Serial{
+            path: String::from(path),
+            port: Serial::open_port(path)?,
+        })
+    }
+
+
+    // 이미 열려 있는 포트를 사용하는 경우 (pty 등)
+    pub fn from_port(port: Box<dyn SerialPort>) -> Serial
+    {
+        Serial{
+            path: port.name().unwrap_or_default(),
+            port,
+        }
+    }
+
+
+    // 장치가 다시 연결되었을 때 같은 경로로 포트를 다시 연다
+    pub fn reconnect(&mut self) -> io::Result<()>
+    {
+        self.port = Serial::open_port(&self.path)?;
+
+        Ok(())
+    }
+
+
+    fn open_port(path: &str) -> io::Result<Box<dyn SerialPort>>
+    {
+        let port = serialport::new(path, Serial::BAUD_RATE)
+            .data_bits(DataBits::Eight)
+            .parity(Parity::None)
+            .stop_bits(StopBits::One)
+            .flow_control(FlowControl::None)
+            .timeout(Duration::from_millis(Serial::TIMEOUT_MS))
+            .open()?;
+
+        Ok(port)
+    }
+}
+
+
+impl Transport for Serial
+{
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+    {
+        self.port.read(buffer)
+    }
+
+    fn write(&mut self, slice_data: &[u8]) -> io::Result<()>
+    {
+        self.port.write_all(slice_data)?;
+        self.port.flush()
+    }
+
+    fn reconnect(&mut self) -> io::Result<()>
+    {
+        Serial::reconnect(self)
+    }
+}
+
+
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serialport::TTYPort;
+
+    use crate::Drone;
+    use crate::protocol::{Data, DataType};
+    use crate::system::DeviceType;
+
+    #[test]
+    fn pty_pair() {
+        let (mut master, slave) = TTYPort::pair().unwrap();
+        master.set_timeout(Duration::from_millis(100)).unwrap();
+
+        let mut drone = Drone::with_transport(Serial::from_port(Box::new(slave)));
+
+        // 전송
+        let vec_data = drone.landing();
+        let mut buffer = vec![0u8; vec_data.len()];
+        master.read_exact(&mut buffer).unwrap();
+        assert_eq!(buffer, vec_data);
+
+        // 수신
+        let vec_data = drone.request(DeviceType::Drone, DataType::Attitude);
+        master.write_all(&vec_data).unwrap();
+
+        let mut length = 0;
+        for _ in 0..100 {
+            length += drone.poll().unwrap();
+            if length >= vec_data.len() {
+                break;
+            }
+        }
+
+        assert!(matches!(drone.check().unwrap().to_data(), Ok(Data::Request(_))));
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::system::{*};
 use crate::communication::crc16;
 use crate::protocol::{*};
@@ -43,6 +45,25 @@ pub fn transfer_header_data(header: &Header, data: &Vec<u8>) -> Vec<u8>
 }
 
 
+// 받은 헤더를 그대로 사용 (정의되지 않은 DataType, DeviceType을 전달할 때 사용)
+pub fn transfer_raw(header_raw: &[u8; 4], data: &[u8]) -> Vec<u8>
+{
+    // start
+    let mut vec_data: Vec<u8> = start();
+
+    // header
+    vec_data.extend_from_slice(header_raw);
+
+    // data
+    vec_data.extend_from_slice(data);
+
+    // crc16
+    add_crc16(&mut vec_data);
+
+    vec_data
+}
+
+
 pub fn transfer(data_type: DataType, from: DeviceType, to: DeviceType, data: &Vec<u8>) -> Vec<u8>
 {
     // start
@@ -64,24 +85,67 @@ pub fn transfer(data_type: DataType, from: DeviceType, to: DeviceType, data: &Ve
 }
 
 
+// Payload에 연결된 DataType으로 전송할 데이터 생성
+pub fn build<T: Payload>(from: DeviceType, to: DeviceType, data: &T) -> Vec<u8>
+{
+    transfer(T::DATA_TYPE, from, to, &data.to_vec())
+}
+
+
+// -- RetryPolicy ----------------------------------------------------------------------------------------------
+// Ack를 받지 못했을 때 재전송 설정. 대기 시간은 재전송할 때마다 backoff배씩 늘어남
+#[derive(Debug, Copy, Clone)]
+pub struct RetryPolicy {
+    pub count_try: u32,
+    pub time_timeout: Duration,
+    pub backoff: u32,
+}
+
+
+impl RetryPolicy {
+    pub fn new() -> RetryPolicy
+    {
+        RetryPolicy{
+            count_try: 5,
+            time_timeout: Duration::from_millis(100),
+            backoff: 2,
+        }
+    }
+
+
+    pub fn get_timeout(&self, index_try: u32) -> Duration
+    {
+        self.time_timeout * self.backoff.saturating_pow(index_try)
+    }
+}
+
+
+impl Default for RetryPolicy {
+    fn default() -> Self
+    {
+        RetryPolicy::new()
+    }
+}
+
+
 // -- Request ----------------------------------------------------------------------------------------------
 pub fn request(target: DeviceType, data_type: DataType) -> Vec<u8>
 {
-    transfer(DataType::Request, DeviceType::Base, target, &Request{data_type}.to_vec())
+    build(DeviceType::Base, target, &Request{data_type})
 }
 
 
 // -- Command ----------------------------------------------------------------------------------------------
 pub fn command(target: DeviceType, command_type: CommandType, option: u8) -> Vec<u8>
 {
-    transfer(DataType::Command, DeviceType::Base, target, &Command{command_type, option}.to_vec())
+    build(DeviceType::Base, target, &Command{command_type, option})
 }
 
 
 // -- FlightEvent ----------------------------------------------------------------------------------------------
 pub fn flight_event(event: FlightEvent) -> Vec<u8>
 {
-    transfer(DataType::Command, DeviceType::Base, DeviceType::Drone, &Command{command_type: CommandType::FlightEvent, option: event.into()}.to_vec())
+    build(DeviceType::Base, DeviceType::Drone, &Command{command_type: CommandType::FlightEvent, option: event.into()})
 }
 
 pub fn takeoff() -> Vec<u8>
@@ -128,68 +192,81 @@ pub fn clear_trim() -> Vec<u8>
 
 pub fn trim(roll: i16, pitch: i16, yaw: i16, throttle: i16) -> Vec<u8>
 {
-    transfer(DataType::Trim, DeviceType::Base, DeviceType::Drone, &sensor::Trim{roll, pitch, yaw, throttle}.to_vec())
+    build(DeviceType::Base, DeviceType::Drone, &sensor::Trim{roll, pitch, yaw, throttle})
+}
+
+pub fn set_weight(weight: f32) -> Vec<u8>
+{
+    build(DeviceType::Base, DeviceType::Drone, &sensor::Weight{weight})
+}
+
+
+// -- Echo ----------------------------------------------------------------------------------------------
+// 받은 데이터를 그대로 돌려받아 송수신 가능한 데이터 길이를 확인
+pub fn echo(target: DeviceType, slice_data: &[u8]) -> Vec<u8>
+{
+    build(DeviceType::Base, target, &Echo{vec_data: slice_data.to_vec()})
 }
 
 
 // -- Control ----------------------------------------------------------------------------------------------
 pub fn control(roll: i8, pitch: i8, yaw: i8, throttle: i8) -> Vec<u8>
 {
-    transfer(DataType::Control, DeviceType::Base, DeviceType::Drone, &control::Quad8{roll, pitch, yaw, throttle}.to_vec())
+    build(DeviceType::Base, DeviceType::Drone, &control::Quad8{roll, pitch, yaw, throttle})
 }
 
 pub fn control_request(roll: i8, pitch: i8, yaw: i8, throttle: i8, data_type: DataType) -> Vec<u8>
 {
-    transfer(DataType::Control, DeviceType::Base, DeviceType::Drone, &control::Quad8AndRequestData{roll, pitch, yaw, throttle, data_type}.to_vec())
+    build(DeviceType::Base, DeviceType::Drone, &control::Quad8AndRequestData{roll, pitch, yaw, throttle, data_type})
 }
 
 pub fn control_position(x: f32, y: f32, z: f32, velocity: f32, heading: i16, rotational_velocity: i16) -> Vec<u8>
 {
-    transfer(DataType::Control, DeviceType::Base, DeviceType::Drone, &control::Position{x, y, z, velocity, heading, rotational_velocity}.to_vec())
+    build(DeviceType::Base, DeviceType::Drone, &control::Position{x, y, z, velocity, heading, rotational_velocity})
 }
 
 
 // -- Battle ----------------------------------------------------------------------------------------------
 pub fn battle_ir_message(ir_message: u8) -> Vec<u8>
 {
-    transfer(DataType::Battle, DeviceType::Base, DeviceType::Drone, &battle::IrMessage{ir_message}.to_vec())
+    build(DeviceType::Base, DeviceType::Drone, &battle::IrMessage{ir_message})
 }
 
 pub fn battle_light_event_command(target:DeviceType, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8, command_type: command::CommandType, option: u8) -> Vec<u8>
 {
-    transfer(DataType::Battle, DeviceType::Base, target, &battle::LightEventCommand{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}}.to_vec())
+    build(DeviceType::Base, target, &battle::LightEventCommand{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}})
 }
 
 pub fn battle_ir_message_light_event_command(target:DeviceType, ir_message: u8, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8, command_type: command::CommandType, option: u8) -> Vec<u8>
 {
-    transfer(DataType::Battle, DeviceType::Base, target, &battle::IrMessageLightEventCommand{ir_message, event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}}.to_vec())
+    build(DeviceType::Base, target, &battle::IrMessageLightEventCommand{ir_message, event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}})
 }
 
 
 // -- Light ----------------------------------------------------------------------------------------------
 pub fn light_manual(target:DeviceType, flags: u16, brightness: u8) -> Vec<u8>
 {
-    transfer(DataType::LightManual, DeviceType::Base, target, &light::Manual{flags, brightness}.to_vec())
+    build(DeviceType::Base, target, &light::Manual{flags, brightness})
 }
 
 pub fn light_mode(target:DeviceType, mode: u8, interval: u16) -> Vec<u8>
 {
-    transfer(DataType::LightMode, DeviceType::Base, target, &light::Mode{mode, interval}.to_vec())
+    build(DeviceType::Base, target, &light::Mode{mode, interval})
 }
 
 pub fn light_event(target:DeviceType, event: u8, interval: u16, repeat: u8) -> Vec<u8>
 {
-    transfer(DataType::LightEvent, DeviceType::Base, target, &light::Event{event, interval, repeat}.to_vec())
+    build(DeviceType::Base, target, &light::Event{event, interval, repeat})
 }
 
 pub fn light_mode_color(target:DeviceType, mode: u8, interval: u16, r: u8, g: u8, b: u8) -> Vec<u8>
 {
-    transfer(DataType::LightMode, DeviceType::Base, target, &light::ModeColor{mode:light::Mode{mode, interval}, color: light::Color{r, g, b}}.to_vec())
+    build(DeviceType::Base, target, &light::ModeColor{mode:light::Mode{mode, interval}, color: light::Color{r, g, b}})
 }
 
 pub fn light_event_color(target:DeviceType, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8) -> Vec<u8>
 {
-    transfer(DataType::LightEvent, DeviceType::Base, target, &light::EventColor{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}}.to_vec())
+    build(DeviceType::Base, target, &light::EventColor{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}})
 }
 
 pub fn light_default(target:DeviceType, mode: u8, interval: u16, r: u8, g: u8, b: u8) -> Vec<u8>
@@ -199,114 +276,130 @@ pub fn light_default(target:DeviceType, mode: u8, interval: u16, r: u8, g: u8, b
 
 
 // -- Buzzer ----------------------------------------------------------------------------------------------
+pub fn buzzer_stop(target: DeviceType) -> Vec<u8>
+{
+    build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::Stop, hz: 0, time: 0})
+}
+
 pub fn buzzer_scale(target: DeviceType, scale: buzzer::Scale, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleInstantly, scale, time}.to_vec())
+    build(DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleInstantly, scale, time})
 }
 
 pub fn buzzer_scale_reserve(target: DeviceType, scale: buzzer::Scale, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleContinually, scale, time}.to_vec())
+    build(DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleContinually, scale, time})
 }
 
 pub fn buzzer_hz(target: DeviceType, hz: u16, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzInstantly, hz, time}.to_vec())
+    build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzInstantly, hz, time})
 }
 
 pub fn buzzer_hz_reserve(target: DeviceType, hz: u16, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzContinually, hz, time}.to_vec())
+    build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzContinually, hz, time})
 }
 
 pub fn buzzer_mute(target: DeviceType, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteInstantly, hz: 0, time}.to_vec())
+    build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteInstantly, hz: 0, time})
 }
 
 pub fn buzzer_mute_reserve(target: DeviceType, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteContinually, hz: 0, time}.to_vec())
+    build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteContinually, hz: 0, time})
 }
 
 
 // -- Vibrator ----------------------------------------------------------------------------------------------
 pub fn vibrator(on: u16, off: u16, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Vibrator, DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Instantly, on, off, time}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Instantly, on, off, time})
 }
 
 pub fn vibrator_reserve(on: u16, off: u16, time: u16) -> Vec<u8>
 {
-    transfer(DataType::Vibrator, DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Continually, on, off, time}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Continually, on, off, time})
 }
 
 
 // -- Display ----------------------------------------------------------------------------------------------
 pub fn draw_clear_all(pixel: Pixel) -> Vec<u8>
 {
-    transfer(DataType::DisplayClear, DeviceType::Base, DeviceType::Controller, &ClearAll{pixel}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &ClearAll{pixel})
 }
 
 pub fn draw_clear(x: i16, y: i16, width: i16, height: i16, pixel: Pixel) -> Vec<u8>
 {
-    transfer(DataType::DisplayClear, DeviceType::Base, DeviceType::Controller, &Clear{x, y, width, height, pixel}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &Clear{x, y, width, height, pixel})
 }
 
 pub fn draw_invert(x: i16, y: i16, width: i16, height: i16) -> Vec<u8>
 {
-    transfer(DataType::DisplayInvert, DeviceType::Base, DeviceType::Controller, &Invert{x, y, width, height}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &Invert{x, y, width, height})
 }
 
 pub fn draw_point(x: i16, y: i16, pixel: Pixel) -> Vec<u8>
 {
-    transfer(DataType::DisplayDrawPoint, DeviceType::Base, DeviceType::Controller, &DrawPoint{x, y, pixel}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &DrawPoint{x, y, pixel})
 }
 
 pub fn draw_line(x1: i16, y1: i16, x2: i16, y2: i16, pixel: Pixel, line: Line) -> Vec<u8>
 {
-    transfer(DataType::DisplayDrawLine, DeviceType::Base, DeviceType::Controller, &DrawLine{x1, y1, x2, y2, pixel, line}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &DrawLine{x1, y1, x2, y2, pixel, line})
 }
 
 pub fn draw_rect(x: i16, y: i16, width: i16, height: i16, pixel: Pixel, fill: bool, line: Line) -> Vec<u8>
 {
-    transfer(DataType::DisplayDrawRect, DeviceType::Base, DeviceType::Controller, &DrawRect{x, y, width, height, pixel, fill, line}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &DrawRect{x, y, width, height, pixel, fill, line})
 }
 
 pub fn draw_circle(x: i16, y: i16, radius: i16, pixel: Pixel, fill: bool) -> Vec<u8>
 {
-    transfer(DataType::DisplayDrawCircle, DeviceType::Base, DeviceType::Controller, &DrawCircle{x, y, radius, pixel, fill}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &DrawCircle{x, y, radius, pixel, fill})
 }
 
 pub fn draw_string(x: i16, y: i16, font: Font, pixel: Pixel, string: String) -> Vec<u8>
 {
-    transfer(DataType::DisplayDrawString, DeviceType::Base, DeviceType::Controller, &DrawString{x, y, font, pixel, string}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &DrawString{x, y, font, pixel, string})
 }
 
 pub fn draw_string_align(x_start: i16, x_end: i16, y: i16, align: Align, font: Font, pixel: Pixel, string: String) -> Vec<u8>
 {
-    transfer(DataType::DisplayDrawStringAlign, DeviceType::Base, DeviceType::Controller, &DrawStringAlign{x_start, x_end, y, align, font, pixel, string}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &DrawStringAlign{x_start, x_end, y, align, font, pixel, string})
 }
 
 pub fn draw_image(x: i16, y: i16, width: i16, height: i16, vec_image: Vec<u8>) -> Vec<u8>
 {
-    transfer(DataType::DisplayDrawImage, DeviceType::Base, DeviceType::Controller, &DrawImage{x, y, width, height, vec_image}.to_vec())
+    build(DeviceType::Base, DeviceType::Controller, &DrawImage{x, y, width, height, vec_image})
+}
+
+
+// -- GpsRtk ----------------------------------------------------------------------------------------------
+// 드론을 RTK 기준국(Base) 또는 이동국(Rover)으로 설정
+pub fn gps_rtk_base(enable: bool) -> Vec<u8>
+{
+    command(DeviceType::Drone, CommandType::GpsRtkBase, enable as u8)
+}
+
+pub fn gps_rtk_rover(enable: bool) -> Vec<u8>
+{
+    command(DeviceType::Drone, CommandType::GpsRtkRover, enable as u8)
 }
 
 
 // -- ExternalSystemCommand ----------------------------------------------------------------------------------------------
-/*
+// Raspberry PI 등 외부 장치 제어. 응답은 Profile::External로 해석
 pub fn external_system_command(target: DeviceType, command_type: external::system::CommandType) -> Vec<u8>
 {
-    transfer(DataType::ExternalSystemCommand, DeviceType::Base, target, &external::system::Command{command_type}.to_vec())
+    build(DeviceType::Base, target, &external::system::Command{command_type})
 }
 
 
 // -- ExternalCameraCommand ----------------------------------------------------------------------------------------------
 pub fn external_camera_command(target: DeviceType, command_type: external::camera::CommandType) -> Vec<u8>
 {
-    transfer(DataType::ExternalCameraCommand, DeviceType::Base, target, &external::camera::Command{command_type}.to_vec())
+    build(DeviceType::Base, target, &external::camera::Command{command_type})
 }
 
-// */
-
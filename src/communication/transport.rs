@@ -0,0 +1,98 @@
+use std::io;
+use std::collections::VecDeque;
+
+
+// -- Transport ----------------------------------------------------------------------------------------------
+// 드론(또는 조종기)과 바이트를 주고 받는 통로
+// read는 읽을 데이터가 없으면 Ok(0)을 반환하거나 WouldBlock / TimedOut 에러를 반환할 수 있음
+pub trait Transport
+{
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>;
+    fn write(&mut self, slice_data: &[u8]) -> io::Result<()>;
+
+    // 장치가 재시작된 후 다시 연결 (펌웨어 업데이트 중 부트로더/앱으로 이동할 때 사용)
+    fn reconnect(&mut self) -> io::Result<()>
+    {
+        Ok(())
+    }
+}
+
+
+impl<T: Transport + ?Sized> Transport for Box<T>
+{
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+    {
+        (**self).read(buffer)
+    }
+
+    fn write(&mut self, slice_data: &[u8]) -> io::Result<()>
+    {
+        (**self).write(slice_data)
+    }
+
+    fn reconnect(&mut self) -> io::Result<()>
+    {
+        (**self).reconnect()
+    }
+}
+
+
+// -- NoTransport ----------------------------------------------------------------------------------------------
+// 연결된 통로가 없는 경우. 전송할 데이터는 버리고, 수신 데이터는 push / push_slice로 직접 넣어야 함
+#[derive(Debug, Default)]
+pub struct NoTransport;
+
+
+impl Transport for NoTransport
+{
+    fn read(&mut self, _buffer: &mut [u8]) -> io::Result<usize>
+    {
+        Ok(0)
+    }
+
+    fn write(&mut self, _slice_data: &[u8]) -> io::Result<()>
+    {
+        Ok(())
+    }
+}
+
+
+// -- Loopback ----------------------------------------------------------------------------------------------
+// 메모리 상에서 전송한 데이터를 그대로 다시 읽어오는 통로 (테스트용)
+#[derive(Debug, Default)]
+pub struct Loopback {
+    pub queue_buffer: VecDeque<u8>,
+}
+
+
+impl Loopback {
+    pub fn new() -> Loopback
+    {
+        Loopback{
+            queue_buffer: VecDeque::new(),
+        }
+    }
+}
+
+
+impl Transport for Loopback
+{
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+    {
+        let length = buffer.len().min(self.queue_buffer.len());
+
+        for (i, b) in self.queue_buffer.drain(..length).enumerate()
+        {
+            buffer[i] = b;
+        }
+
+        Ok(length)
+    }
+
+    fn write(&mut self, slice_data: &[u8]) -> io::Result<()>
+    {
+        self.queue_buffer.extend(slice_data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,447 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::file::{Compatibility, EncryptedBinary};
+use crate::protocol::{*};
+use crate::protocol::command::CommandType;
+use crate::system::{*};
+use crate::communication::transfer;
+
+
+// -- Failure ----------------------------------------------------------------------------------------------
+// 업데이트 실패 원인
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Timeout,                                                        // 재전송 횟수를 넘길 때까지 응답 없음
+    Incompatible(Compatibility),                                    // 펌웨어 파일을 장치에 쓸 수 없음 (모델, 길이 불일치)
+    ModeUpdate(ModeUpdate),                                         // 업데이트할 수 없는 상태
+}
+
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Failure::Timeout => write!(f, "No response from device"),
+            Failure::Incompatible(compatibility) => write!(f, "Incompatible firmware ({:?})", compatibility),
+            Failure::ModeUpdate(mode_update) => write!(f, "Not updatable (ModeUpdate: {:?})", mode_update),
+        }
+    }
+}
+
+
+// -- State ----------------------------------------------------------------------------------------------
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum State {
+    Ready,              // 시작 전
+    Information,        // Information 요청 후 응답 대기
+    JumpToBootloader,   // 부트로더로 이동 명령 후 장치 재시작 대기
+    Update,             // Update 전송 후 다음 위치(UpdateLocation) 대기
+    Verify,             // 마지막 블럭 전송 후 업데이트 완료(ModeUpdate::Complete) 확인
+    JumpToApplication,  // 앱으로 이동 명령 후 장치 재시작 대기
+    Complete,           // 업데이트 완료
+    Failed(Failure),    // 업데이트 실패
+}
+
+
+// -- Action ----------------------------------------------------------------------------------------------
+// UpdateSession을 사용하는 쪽에서 처리해야 할 동작
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Transfer(Vec<u8>),  // 장치로 전송
+    Reconnect,          // 장치가 재시작 되었으므로 다시 연결한 후 reconnected() 호출
+}
+
+
+// -- Progress ----------------------------------------------------------------------------------------------
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub index_block: u32,           // 장치가 요청한 다음 블럭 (전송 완료한 블럭 수)
+    pub count_block_total: u32,     // 전체 블럭 수 (블럭 하나는 16 byte)
+}
+
+
+impl Progress {
+    // 0.0 ~ 1.0
+    pub fn get_ratio(&self) -> f32
+    {
+        if self.count_block_total > 0 { self.index_block.min(self.count_block_total) as f32 / self.count_block_total as f32 } else { 0.0 }
+    }
+}
+
+
+// -- UpdateSession ----------------------------------------------------------------------------------------------
+// 펌웨어 업데이트 진행 상태
+// 송수신은 하지 않고, 받은 데이터(push)와 시간 경과(check)에 따라 전송할 데이터(Action)를 반환함
+// Drone::update_firmware()로 실행하거나 직접 Action을 처리하여 사용
+//
+// 1. Information 요청. 앱이 동작 중이면(RunApplication) 부트로더로 이동하고 다시 연결한 후 재요청
+// 2. ModeUpdate::Ready이고 파일을 장치에 쓸 수 있으면(check_compatible) UpdateLocation 요청
+// 3. 장치가 UpdateLocation으로 알려준 위치의 블럭을 Update로 전송 (응답이 없으면 재전송)
+// 4. 마지막 블럭까지 전송하면 Information으로 완료 확인 후 앱으로 이동
+#[derive(Debug)]
+pub struct UpdateSession {
+    pub target: DeviceType,
+    pub binary: EncryptedBinary,
+    pub count_block: u16,                       // Update 하나에 담을 블럭 수
+    pub retry_policy: transfer::RetryPolicy,    // 응답이 없을 때 재전송 설정
+    pub time_reboot: Duration,                  // 이동 명령 후 장치가 재시작할 때까지 기다리는 시간
+
+    state: State,
+    progress: Progress,
+    flag_jumped: bool,                          // 부트로더로 이동한 적이 있음
+
+    vec_transfer: Vec<u8>,                      // 마지막으로 전송한 데이터 (재전송용)
+    time_transfer: Duration,
+    index_try: u32,
+}
+
+
+impl UpdateSession {
+    pub fn new(target: DeviceType, binary: EncryptedBinary) -> UpdateSession
+    {
+        let count_block_total = binary.get_length().div_ceil(16) as u32;
+
+        UpdateSession{
+            target,
+            binary,
+            count_block: 4,
+            retry_policy: transfer::RetryPolicy{ count_try: 5, time_timeout: Duration::from_millis(300), backoff: 1 },
+            time_reboot: Duration::from_millis(2000),
+
+            state: State::Ready,
+            progress: Progress{ index_block: 0, count_block_total },
+            flag_jumped: false,
+
+            vec_transfer: Vec::new(),
+            time_transfer: Duration::ZERO,
+            index_try: 0,
+        }
+    }
+
+
+    pub fn get_state(&self) -> State
+    {
+        self.state
+    }
+
+
+    pub fn get_progress(&self) -> Progress
+    {
+        self.progress
+    }
+
+
+    // 완료 또는 실패
+    pub fn is_finished(&self) -> bool
+    {
+        matches!(self.state, State::Complete | State::Failed(_))
+    }
+
+
+    pub fn start(&mut self, time_now: Duration) -> Action
+    {
+        self.flag_jumped = false;
+        self.progress.index_block = 0;
+
+        self.request_information(time_now)
+    }
+
+
+    // 장치에서 받은 데이터 처리
+    pub fn push(&mut self, header: &Header, data: &Data, time_now: Duration) -> Action
+    {
+        if header.from != self.target {
+            return Action::None;
+        }
+
+        match (self.state, data) {
+            (State::Information, Data::Information(information)) => {
+                match information.mode_update {
+                    ModeUpdate::RunApplication if !self.flag_jumped => {
+                        self.flag_jumped = true;
+                        self.state = State::JumpToBootloader;
+                        self.time_transfer = time_now;
+                        Action::Transfer(transfer::command(self.target, CommandType::JumpToBootloader, 0))
+                    },
+                    ModeUpdate::Ready | ModeUpdate::Update => {
+                        // 버전은 비교하지 않음 (부트로더에서는 부트로더의 버전을 보냄)
+                        let compatibility = self.binary.check_compatible(information);
+                        if !compatibility.is_compatible() {
+                            return self.fail(Failure::Incompatible(compatibility));
+                        }
+
+                        self.state = State::Update;
+                        self.transfer(transfer::request(self.target, DataType::UpdateLocation), time_now)
+                    },
+                    mode_update => self.fail(Failure::ModeUpdate(mode_update)),
+                }
+            },
+
+            (State::Update, Data::UpdateLocation(location)) => {
+                self.progress.index_block = location.index_block_next as u32;
+
+                match self.binary.get_data_block(location.index_block_next, self.count_block) {
+                    Some(vec_data) => {
+                        let update = Update{ index_block_next: location.index_block_next, vec_data };
+                        self.transfer(transfer::build(DeviceType::Base, self.target, &update), time_now)
+                    },
+                    None => {
+                        // 요청한 위치가 파일 끝을 넘어가면 전송 완료
+                        self.progress.index_block = self.progress.count_block_total;
+                        self.state = State::Verify;
+                        self.transfer(transfer::request(self.target, DataType::Information), time_now)
+                    },
+                }
+            },
+
+            (State::Verify, Data::Information(information)) => {
+                match information.mode_update {
+                    ModeUpdate::Complete => {
+                        self.state = State::JumpToApplication;
+                        self.time_transfer = time_now;
+                        Action::Transfer(transfer::command(self.target, CommandType::JumpToApplication, 0))
+                    },
+                    ModeUpdate::Update => Action::None,     // 아직 쓰는 중이면 재전송 시간까지 대기
+                    mode_update => self.fail(Failure::ModeUpdate(mode_update)),
+                }
+            },
+
+            _ => Action::None,
+        }
+    }
+
+
+    // 시간 경과 처리. 응답이 없으면 재전송하고 장치 재시작 대기 시간이 지나면 다시 연결 요청
+    pub fn check(&mut self, time_now: Duration) -> Action
+    {
+        let time_passed = time_now.saturating_sub(self.time_transfer);
+
+        match self.state {
+            State::Information | State::Update | State::Verify => {
+                if time_passed <= self.retry_policy.get_timeout(self.index_try) {
+                    return Action::None;
+                }
+
+                self.index_try += 1;
+                if self.index_try >= self.retry_policy.count_try {
+                    return self.fail(Failure::Timeout);
+                }
+
+                self.time_transfer = time_now;
+                Action::Transfer(self.vec_transfer.clone())
+            },
+
+            State::JumpToBootloader | State::JumpToApplication if time_passed > self.time_reboot => Action::Reconnect,
+
+            _ => Action::None,
+        }
+    }
+
+
+    // Action::Reconnect 처리 후 호출
+    pub fn reconnected(&mut self, time_now: Duration) -> Action
+    {
+        match self.state {
+            State::JumpToBootloader => self.request_information(time_now),
+            State::JumpToApplication => {
+                self.state = State::Complete;
+                Action::None
+            },
+            _ => Action::None,
+        }
+    }
+
+
+    fn request_information(&mut self, time_now: Duration) -> Action
+    {
+        self.state = State::Information;
+        self.transfer(transfer::request(self.target, DataType::Information), time_now)
+    }
+
+
+    fn transfer(&mut self, vec_data: Vec<u8>, time_now: Duration) -> Action
+    {
+        self.vec_transfer = vec_data.clone();
+        self.time_transfer = time_now;
+        self.index_try = 0;
+
+        Action::Transfer(vec_data)
+    }
+
+
+    fn fail(&mut self, failure: Failure) -> Action
+    {
+        self.state = State::Failed(failure);
+        Action::None
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::collections::VecDeque;
+
+    use crate::Drone;
+    use crate::file::EncryptedBinaryHeader;
+    use crate::communication::messaging;
+    use crate::communication::receiver::Receiver;
+    use crate::communication::transport::Transport;
+
+    // 정해진 순서대로 응답하는 부트로더
+    struct FakeBootloader {
+        receiver: Receiver,
+        queue_buffer: VecDeque<u8>,
+        model_number: ModelNumber,
+        mode_update: ModeUpdate,
+        mode_update_next: ModeUpdate,       // 다시 연결한 후의 상태
+        length: usize,
+        vec_flash: Vec<u8>,
+        count_drop: u32,                    // 응답하지 않고 버릴 Update 수
+        count_reconnect: u32,
+    }
+
+    impl FakeBootloader {
+        fn new(model_number: ModelNumber, length: usize) -> FakeBootloader
+        {
+            FakeBootloader{
+                receiver: Receiver::new(),
+                queue_buffer: VecDeque::new(),
+                model_number,
+                mode_update: ModeUpdate::RunApplication,
+                mode_update_next: ModeUpdate::RunApplication,
+                length,
+                vec_flash: Vec::new(),
+                count_drop: 1,
+                count_reconnect: 0,
+            }
+        }
+
+        fn reply<P: Payload>(&mut self, data: &P)
+        {
+            self.queue_buffer.extend(transfer::build(DeviceType::Drone, DeviceType::Base, data));
+        }
+
+        fn handle(&mut self, data: Data)
+        {
+            let index_block_next = self.vec_flash.len().div_ceil(16) as u16;
+
+            match data {
+                Data::Request(Request{data_type: DataType::Information}) => {
+                    let information = Information{mode_update: self.mode_update, model_number: self.model_number, version: Version{build: 1, minor: 6, major: 22}, year: 2022, month: 6, day: 2};
+                    self.reply(&information);
+                },
+                Data::Request(Request{data_type: DataType::UpdateLocation}) => {
+                    self.reply(&UpdateLocation{index_block_next});
+                },
+                Data::Command(command) if command.command_type == CommandType::JumpToBootloader => {
+                    self.mode_update_next = ModeUpdate::Ready;
+                },
+                Data::Command(command) if command.command_type == CommandType::JumpToApplication => {
+                    self.mode_update_next = ModeUpdate::RunApplication;
+                },
+                Data::Update(update) => {
+                    if self.count_drop > 0 {
+                        self.count_drop -= 1;
+                        return;
+                    }
+
+                    if update.index_block_next == index_block_next {
+                        self.vec_flash.extend(&update.vec_data);
+                        self.mode_update = if self.vec_flash.len() >= self.length { ModeUpdate::Complete } else { ModeUpdate::Update };
+                    }
+
+                    self.reply(&UpdateLocation{index_block_next: self.vec_flash.len().div_ceil(16) as u16});
+                },
+                _ => {},
+            }
+        }
+    }
+
+    impl Transport for FakeBootloader {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+        {
+            let length = buffer.len().min(self.queue_buffer.len());
+
+            for (i, b) in self.queue_buffer.drain(..length).enumerate()
+            {
+                buffer[i] = b;
+            }
+
+            Ok(length)
+        }
+
+        fn write(&mut self, slice_data: &[u8]) -> io::Result<()>
+        {
+            self.receiver.push_slice(slice_data);
+
+            while let messaging::State::Loaded = self.receiver.check()
+            {
+                self.receiver.clear();
+
+                if let Ok(data) = self.receiver.get_frame().to_data()
+                {
+                    self.handle(data);
+                }
+            }
+
+            Ok(())
+        }
+
+        fn reconnect(&mut self) -> io::Result<()>
+        {
+            self.count_reconnect += 1;
+            self.mode_update = self.mode_update_next;
+            self.queue_buffer.clear();
+
+            Ok(())
+        }
+    }
+
+    fn binary(model_number: ModelNumber, length: usize) -> EncryptedBinary
+    {
+        let header = EncryptedBinaryHeader{model_number, ..EncryptedBinaryHeader::new()};
+        let payload: Vec<u8> = (0..length).map(|i| i as u8).collect();
+
+        EncryptedBinary::from_payload(header, &payload)
+    }
+
+    fn session(binary: EncryptedBinary) -> UpdateSession
+    {
+        let mut session = UpdateSession::new(DeviceType::Drone, binary);
+        session.retry_policy.time_timeout = Duration::from_millis(20);
+        session.time_reboot = Duration::from_millis(5);
+
+        session
+    }
+
+    #[test]
+    fn update_firmware() {
+        let mut drone = Drone::with_transport(FakeBootloader::new(ModelNumber::Drone4DroneP6, 208));
+        let mut session = session(binary(ModelNumber::Drone4DroneP6, 184));
+
+        let mut vec_progress = Vec::new();
+        let state = drone.update_firmware(&mut session, |progress| vec_progress.push(progress.index_block)).unwrap();
+
+        assert_eq!(state, State::Complete);
+        assert_eq!(drone.transport.vec_flash, session.binary.data_array);
+        assert_eq!(drone.transport.count_reconnect, 2);
+        assert_eq!(drone.transport.mode_update, ModeUpdate::RunApplication);
+        assert_eq!(vec_progress, vec![4, 8, 12, 13]);
+        assert_eq!(session.get_progress().get_ratio(), 1.0);
+    }
+
+    #[test]
+    fn model_mismatch() {
+        let mut drone = Drone::with_transport(FakeBootloader::new(ModelNumber::Drone4DroneP5, 208));
+        let mut session = session(binary(ModelNumber::Drone4DroneP6, 184));
+
+        let state = drone.update_firmware(&mut session, |_| {}).unwrap();
+        assert_eq!(state, State::Failed(Failure::Incompatible(Compatibility::ModelMismatch{ expected: ModelNumber::Drone4DroneP6, actual: ModelNumber::Drone4DroneP5 })));
+        assert!(drone.transport.vec_flash.is_empty());
+    }
+}
@@ -1,10 +1,85 @@
+use std::fmt;
+use std::io;
 use std::fs::File;
 use std::io::prelude::{*};
+use std::cmp::Ordering;
 
 use crate::system::{*};
+use crate::protocol::Information;
 use crate::communication::extractor::Extractor;
 
 
+// -- FileError ----------------------------------------------------------------------------------------------
+// 펌웨어 파일을 읽지 못한 원인
+#[derive(Debug)]
+pub enum FileError {
+    Io(io::Error),                                  // 파일이 없거나 읽을 수 없음
+    Truncated { expected: usize, actual: usize },   // 헤더와 데이터를 담기에 파일이 짧음 (expected: 최소 길이)
+    Header(&'static str),                           // 헤더를 해석할 수 없음
+    UnknownModel(u32),                              // 정의되지 않은 ModelNumber
+}
+
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            FileError::Io(e) => write!(f, "Cannot read file ({})", e),
+            FileError::Truncated{ expected, actual } => write!(f, "File too short (expected: {}, actual: {})", expected, actual),
+            FileError::Header(e) => write!(f, "Invalid header ({})", e),
+            FileError::UnknownModel(model_number) => write!(f, "Unknown model number ({:#010X})", model_number),
+        }
+    }
+}
+
+
+impl std::error::Error for FileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            FileError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+
+impl From<io::Error> for FileError {
+    fn from(e: io::Error) -> Self
+    {
+        FileError::Io(e)
+    }
+}
+
+
+// -- VersionChange ----------------------------------------------------------------------------------------------
+// 장치의 현재 버전과 비교한 펌웨어 파일의 버전
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VersionChange {
+    Upgrade,
+    Same,
+    Downgrade,
+}
+
+
+// -- Compatibility ----------------------------------------------------------------------------------------------
+// 펌웨어 파일을 장치에 쓸 수 있는지 확인한 결과
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Compatibility {
+    Compatible(VersionChange),
+    ModelMismatch { expected: ModelNumber, actual: ModelNumber },   // expected: 파일, actual: 장치
+    LengthMismatch { expected: usize, actual: usize },              // expected: 헤더에 기록된 길이, actual: 헤더를 제외한 데이터 길이
+}
+
+
+impl Compatibility {
+    pub fn is_compatible(&self) -> bool
+    {
+        matches!(self, Compatibility::Compatible(_))
+    }
+}
+
+
 #[derive(Debug, Copy, Clone)]
 pub struct EncryptedBinaryHeader {
     pub model_number: ModelNumber,  // 4
@@ -47,7 +122,7 @@ impl EncryptedBinaryHeader {
         else { Err("Wrong length") }
     }
 
-    fn to_vec(&self) -> Vec<u8> {
+    pub fn to_vec(&self) -> Vec<u8> {
         let mut vec_data : Vec<u8> = Vec::new();
 
         vec_data.extend_from_slice(&self.model_number.to_array());
@@ -81,35 +156,98 @@ impl EncryptedBinary {
     }
 
 
-    pub fn read(&mut self, file_name: &str) -> bool {
-        // open target file
-        let file = File::open(&file_name);
-
-        match file {
-            Ok(mut f) => {
-                // 파일 전체 읽기
-                match f.read_to_end(&mut self.data_array)
-                {
-                    Ok(length) => {
-                        if length > 16 {
-                            match EncryptedBinaryHeader::parse(&self.data_array[0..16]) {
-                                Ok(header) => {
-                                    self.header = header;
-                                    self.flag_open = true;
-                                    return true;
-                                },
-                                _ => {}
-                            }
-                        }
-                    },
-                    _ => { },
-                }
-            },
-            _ => {}
+    // 헤더와 데이터로 파일 내용 생성
+    // 데이터는 get_data_block()으로 16 byte 블럭 단위로 읽을 수 있도록 0xFF로 채우고, 헤더의 length는 채운 길이로 설정
+    pub fn from_payload(header: EncryptedBinaryHeader, payload: &[u8]) -> EncryptedBinary {
+        let mut eb = EncryptedBinary::new();
+
+        let mut vec_payload = payload.to_vec();
+        vec_payload.resize(payload.len().div_ceil(16) * 16, 0xFF);
+
+        eb.header = header;
+        eb.header.length = vec_payload.len() as u32;
+        eb.data_array = eb.header.to_vec();
+        eb.data_array.extend_from_slice(&vec_payload);
+        eb.flag_open = true;
+
+        eb
+    }
+
+
+    // 데이터는 그대로 두고 헤더만 교체 (날짜, 모델 번호 수정 등). length는 실제 데이터 길이로 유지
+    pub fn set_header(&mut self, header: EncryptedBinaryHeader) {
+        self.header = header;
+        self.header.length = self.get_payload().len() as u32;
+
+        let vec_header = self.header.to_vec();
+        if self.data_array.len() < vec_header.len() {
+            self.data_array.resize(vec_header.len(), 0xFF);
         }
+        self.data_array[..vec_header.len()].copy_from_slice(&vec_header);
+    }
+
 
+    // 헤더를 제외한 데이터
+    pub fn get_payload(&self) -> &[u8] {
+        if self.data_array.len() > EncryptedBinaryHeader::size() { &self.data_array[EncryptedBinaryHeader::size()..] } else { &[] }
+    }
+
+
+    pub fn write(&mut self, file_name: &str) -> Result<(), FileError> {
+        let mut file = File::create(file_name)?;
+        file.write_all(&self.data_array)?;
+
+        self.file_name = String::from(file_name);
+
+        Ok(())
+    }
+
+
+    pub fn read(&mut self, file_name: &str) -> Result<(), FileError> {
+        self.file_name = String::from(file_name);
+        self.data_array.clear();
         self.flag_open = false;
-        false
+
+        // 파일 전체 읽기
+        let mut file = File::open(file_name)?;
+        let length = file.read_to_end(&mut self.data_array)?;
+
+        if length <= EncryptedBinaryHeader::size() {
+            return Err(FileError::Truncated{ expected: EncryptedBinaryHeader::size() + 1, actual: length });
+        }
+
+        // parse는 정의되지 않은 ModelNumber를 ModelNumber::None으로 변환하므로 원래 값으로 확인
+        let header = EncryptedBinaryHeader::parse(&self.data_array[0..EncryptedBinaryHeader::size()])
+            .map_err(FileError::Header)?;
+        if header.model_number == ModelNumber::None {
+            let mut ext: Extractor = Extractor::from_slice(&self.data_array);
+            return Err(FileError::UnknownModel(ext.get_u32()));
+        }
+
+        self.header = header;
+        self.flag_open = true;
+
+        Ok(())
+    }
+
+
+    // 연결된 장치에 쓸 수 있는 파일인지 확인 (모델, 헤더에 기록된 길이, 버전 순서로 비교)
+    pub fn check_compatible(&self, information: &Information) -> Compatibility
+    {
+        if self.header.model_number != information.model_number {
+            return Compatibility::ModelMismatch{ expected: self.header.model_number, actual: information.model_number };
+        }
+
+        let length_body = self.data_array.len().saturating_sub(EncryptedBinaryHeader::size());
+        if self.header.length as usize != length_body {
+            return Compatibility::LengthMismatch{ expected: self.header.length as usize, actual: length_body };
+        }
+
+        match self.header.version.to_u32().cmp(&information.version.to_u32()) {
+            Ordering::Greater => Compatibility::Compatible(VersionChange::Upgrade),
+            Ordering::Equal => Compatibility::Compatible(VersionChange::Same),
+            Ordering::Less => Compatibility::Compatible(VersionChange::Downgrade),
+        }
     }
 
     /*
@@ -154,3 +292,285 @@ impl EncryptedBinary {
     }
 }
 
+
+
+// -- CatalogEntry ----------------------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub file_name: String,
+    pub header: EncryptedBinaryHeader,
+}
+
+
+impl CatalogEntry {
+    // 펌웨어 파일 전체 읽기
+    pub fn load(&self) -> Result<EncryptedBinary, FileError> {
+        let mut eb = EncryptedBinary::new();
+        eb.read(&self.file_name)?;
+
+        Ok(eb)
+    }
+}
+
+
+// -- CatalogIssue ----------------------------------------------------------------------------------------------
+// 목록에 추가하지 않은 파일
+#[derive(Debug)]
+pub enum CatalogIssue {
+    Corrupt { file_name: String, error: FileError },                        // 읽을 수 없는 파일
+    LengthMismatch { file_name: String, expected: usize, actual: usize },   // 헤더에 기록된 길이와 실제 데이터 길이가 다름
+    Duplicate { file_name: String, file_name_kept: String },                // 모델과 버전이 같은 파일이 이미 있음
+}
+
+
+// -- Catalog ----------------------------------------------------------------------------------------------
+// 폴더 안의 펌웨어 파일(.eb) 목록. 모델, 버전 순으로 정렬
+#[derive(Debug, Default)]
+pub struct Catalog {
+    pub vec_entry: Vec<CatalogEntry>,
+    pub vec_issue: Vec<CatalogIssue>,
+}
+
+
+impl Catalog {
+    pub fn new() -> Catalog {
+        Catalog {
+            vec_entry: Vec::new(),
+            vec_issue: Vec::new(),
+        }
+    }
+
+
+    // 폴더 안의 .eb 파일을 이름 순서로 추가 (하위 폴더는 확인하지 않음)
+    pub fn scan(path: &str) -> io::Result<Catalog> {
+        let mut vec_path = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("eb")) {
+                vec_path.push(path);
+            }
+        }
+        vec_path.sort();
+
+        let mut catalog = Catalog::new();
+        for path in vec_path.iter() {
+            catalog.add(&path.to_string_lossy());
+        }
+
+        Ok(catalog)
+    }
+
+
+    // 파일을 읽어 목록에 추가. 추가하지 않은 경우 vec_issue에 원인을 남기고 false 반환
+    pub fn add(&mut self, file_name: &str) -> bool {
+        let mut eb = EncryptedBinary::new();
+        if let Err(error) = eb.read(file_name) {
+            self.vec_issue.push(CatalogIssue::Corrupt{ file_name: String::from(file_name), error });
+            return false;
+        }
+
+        let length_body = eb.get_payload().len();
+        if eb.header.length as usize != length_body {
+            self.vec_issue.push(CatalogIssue::LengthMismatch{ file_name: String::from(file_name), expected: eb.header.length as usize, actual: length_body });
+            return false;
+        }
+
+        let key = (eb.header.model_number as u32, eb.header.version.to_u32());
+        match self.vec_entry.binary_search_by_key(&key, |entry| (entry.header.model_number as u32, entry.header.version.to_u32())) {
+            Ok(index) => {
+                self.vec_issue.push(CatalogIssue::Duplicate{ file_name: String::from(file_name), file_name_kept: self.vec_entry[index].file_name.clone() });
+                false
+            },
+            Err(index) => {
+                self.vec_entry.insert(index, CatalogEntry{ file_name: String::from(file_name), header: eb.header });
+                true
+            },
+        }
+    }
+
+
+    // 모델의 모든 펌웨어 (오래된 버전부터)
+    pub fn get_entries(&self, model_number: ModelNumber) -> Vec<&CatalogEntry> {
+        self.vec_entry.iter().filter(|entry| entry.header.model_number == model_number).collect()
+    }
+
+
+    pub fn get_latest(&self, model_number: ModelNumber) -> Option<&CatalogEntry> {
+        self.vec_entry.iter().rev().find(|entry| entry.header.model_number == model_number)
+    }
+
+
+    // 장치보다 새로운 펌웨어가 있으면 가장 최신 펌웨어 반환
+    pub fn get_update(&self, information: &Information) -> Option<&CatalogEntry> {
+        self.get_latest(information.model_number)
+            .filter(|entry| entry.header.version.to_u32() > information.version.to_u32())
+    }
+
+
+    // 여러 장치의 Information 중 업데이트가 필요한 장치의 순번과 사용할 펌웨어
+    pub fn get_outdated(&self, slice_information: &[Information]) -> Vec<(usize, &CatalogEntry)> {
+        slice_information.iter()
+            .enumerate()
+            .filter_map(|(index, information)| self.get_update(information).map(|entry| (index, entry)))
+            .collect()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    // 테스트가 실패해도 임시 파일(또는 디렉토리)이 남지 않도록 drop에서 삭제
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> TempPath
+        {
+            TempPath(std::env::temp_dir().join(format!("e_drone_{}_{}", name, std::process::id())))
+        }
+
+        fn file_name(&self) -> &str
+        {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl AsRef<Path> for TempPath {
+        fn as_ref(&self) -> &Path
+        {
+            &self.0
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self)
+        {
+            let _ = if self.0.is_dir() { std::fs::remove_dir_all(&self.0) } else { std::fs::remove_file(&self.0) };
+        }
+    }
+
+    fn information(model_number: ModelNumber, version: Version) -> Information
+    {
+        Information{mode_update: ModeUpdate::RunApplication, model_number, version, year: 2022, month: 6, day: 2}
+    }
+
+    #[test]
+    fn check_compatible() {
+        let mut eb = EncryptedBinary::new();
+        eb.header.model_number = ModelNumber::Drone4DroneP6;
+        eb.header.version = Version{major: 22, minor: 6, build: 2};
+        eb.header.length = 32;
+        eb.data_array = vec![0; 16 + 32];
+
+        assert_eq!(eb.check_compatible(&information(ModelNumber::Drone4DroneP6, Version{major: 22, minor: 5, build: 9})), Compatibility::Compatible(VersionChange::Upgrade));
+        assert_eq!(eb.check_compatible(&information(ModelNumber::Drone4DroneP6, Version{major: 22, minor: 6, build: 2})), Compatibility::Compatible(VersionChange::Same));
+        assert_eq!(eb.check_compatible(&information(ModelNumber::Drone4DroneP6, Version{major: 23, minor: 1, build: 0})), Compatibility::Compatible(VersionChange::Downgrade));
+        assert_eq!(eb.check_compatible(&information(ModelNumber::Drone4DroneP5, Version{major: 22, minor: 5, build: 9})),
+                    Compatibility::ModelMismatch{ expected: ModelNumber::Drone4DroneP6, actual: ModelNumber::Drone4DroneP5 });
+
+        eb.data_array.truncate(40);
+        assert_eq!(eb.check_compatible(&information(ModelNumber::Drone4DroneP6, Version{major: 22, minor: 5, build: 9})),
+                    Compatibility::LengthMismatch{ expected: 32, actual: 24 });
+    }
+
+    #[test]
+    fn read_error() {
+        let mut eb = EncryptedBinary::new();
+        let path = TempPath::new("read_error.eb");
+        let file_name = path.file_name();
+
+        assert!(matches!(eb.read(file_name), Err(FileError::Io(_))));
+
+        std::fs::write(&path, [0u8; 16]).unwrap();
+        assert!(matches!(eb.read(file_name), Err(FileError::Truncated{ expected: 17, actual: 16 })));
+
+        let mut vec_data = vec![0u8; 20];
+        vec_data[0..4].copy_from_slice(&0x12345678u32.to_le_bytes());
+        std::fs::write(&path, &vec_data).unwrap();
+        assert!(matches!(eb.read(file_name), Err(FileError::UnknownModel(0x12345678))));
+        assert!(!eb.flag_open);
+
+        vec_data[0..4].copy_from_slice(&ModelNumber::Drone4DroneP6.to_array());
+        std::fs::write(&path, &vec_data).unwrap();
+        assert!(eb.read(file_name).is_ok());
+        assert_eq!(eb.header.model_number, ModelNumber::Drone4DroneP6);
+        assert_eq!(eb.get_length(), 20);
+    }
+
+    #[test]
+    fn write_read() {
+        let header = EncryptedBinaryHeader{model_number: ModelNumber::Drone4DroneP6, version: Version{major: 22, minor: 6, build: 2}, length: 0, year: 2022, month: 6, day: 2};
+        let payload: Vec<u8> = (0..40).collect();
+
+        let mut eb = EncryptedBinary::from_payload(header, &payload);
+        assert_eq!(eb.header.length, 48);
+        assert_eq!(eb.get_length(), 16 + 48);
+        assert_eq!(eb.get_payload()[..40], payload[..]);
+        assert!(eb.get_payload()[40..].iter().all(|b| *b == 0xFF));
+        assert_eq!(eb.get_data_block(3, 1), Some(eb.get_payload()[32..48].to_vec()));
+        assert_eq!(eb.get_data_block(4, 1), None);
+
+        // 날짜만 바꾸어 다시 만들기
+        eb.set_header(EncryptedBinaryHeader{year: 2023, month: 1, day: 15, ..eb.header});
+
+        let path = TempPath::new("write_read.eb");
+        let file_name = path.file_name();
+        eb.write(file_name).unwrap();
+
+        let mut eb_read = EncryptedBinary::new();
+        eb_read.read(file_name).unwrap();
+        assert_eq!(eb_read.data_array, eb.data_array);
+        assert_eq!((eb_read.header.year, eb_read.header.month, eb_read.header.day), (2023, 1, 15));
+        assert_eq!(eb_read.header.length, 48);
+        assert!(eb_read.check_compatible(&information(ModelNumber::Drone4DroneP6, Version{major: 22, minor: 6, build: 2})).is_compatible());
+    }
+
+    #[test]
+    fn catalog() {
+        let temp = TempPath::new("catalog");
+        let path = &temp.0;
+        std::fs::create_dir_all(path).unwrap();
+
+        let write = |file_name: &str, model_number: ModelNumber, version: Version| {
+            let header = EncryptedBinaryHeader{model_number, version, ..EncryptedBinaryHeader::new()};
+            EncryptedBinary::from_payload(header, &[0; 40]).write(path.join(file_name).to_str().unwrap()).unwrap();
+        };
+
+        write("p6_22.5.1.eb", ModelNumber::Drone4DroneP6, Version{major: 22, minor: 5, build: 1});
+        write("p6_22.6.2.eb", ModelNumber::Drone4DroneP6, Version{major: 22, minor: 6, build: 2});
+        write("p6_22.6.2_copy.eb", ModelNumber::Drone4DroneP6, Version{major: 22, minor: 6, build: 2});
+        write("p5_21.1.1.eb", ModelNumber::Drone4DroneP5, Version{major: 21, minor: 1, build: 1});
+        write("p6_22.6.2.txt", ModelNumber::Drone4DroneP6, Version{major: 23, minor: 1, build: 1});
+        std::fs::write(path.join("short.eb"), [0u8; 8]).unwrap();
+
+        let mut eb = EncryptedBinary::from_payload(EncryptedBinaryHeader{model_number: ModelNumber::Drone4DroneP7, ..EncryptedBinaryHeader::new()}, &[0; 40]);
+        eb.data_array.truncate(40);
+        eb.write(path.join("truncated.eb").to_str().unwrap()).unwrap();
+
+        let catalog = Catalog::scan(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(catalog.vec_entry.len(), 3);
+        assert_eq!(catalog.get_entries(ModelNumber::Drone4DroneP6).len(), 2);
+        assert!(catalog.get_latest(ModelNumber::Drone4DroneP6).unwrap().file_name.ends_with("p6_22.6.2.eb"));
+        assert!(catalog.get_latest(ModelNumber::Drone4DroneP7).is_none());
+        assert_eq!(catalog.get_latest(ModelNumber::Drone4DroneP6).unwrap().load().unwrap().header.version, Version{major: 22, minor: 6, build: 2});
+
+        assert_eq!(catalog.vec_issue.len(), 3);
+        assert!(catalog.vec_issue.iter().any(|issue| matches!(issue, CatalogIssue::Duplicate{ file_name, .. } if file_name.ends_with("p6_22.6.2_copy.eb"))));
+        assert!(catalog.vec_issue.iter().any(|issue| matches!(issue, CatalogIssue::Corrupt{ error: FileError::Truncated{ .. }, .. })));
+        assert!(catalog.vec_issue.iter().any(|issue| matches!(issue, CatalogIssue::LengthMismatch{ expected: 48, actual: 24, .. })));
+
+        let vec_information = vec![
+            information(ModelNumber::Drone4DroneP6, Version{major: 22, minor: 5, build: 1}),
+            information(ModelNumber::Drone4DroneP6, Version{major: 22, minor: 6, build: 2}),
+            information(ModelNumber::Drone4DroneP5, Version{major: 20, minor: 8, build: 13}),
+            information(ModelNumber::Drone4DroneP7, Version{major: 20, minor: 8, build: 13}),
+        ];
+        let vec_outdated = catalog.get_outdated(&vec_information);
+        assert_eq!(vec_outdated.iter().map(|(index, _)| *index).collect::<Vec<usize>>(), vec![0, 2]);
+        assert_eq!(vec_outdated[1].1.header.model_number, ModelNumber::Drone4DroneP5);
+    }
+}
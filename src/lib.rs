@@ -1,52 +1,102 @@
 // An attribute to hide warnings for unused code.
 #![allow(dead_code)]
 
+// e_drone_derive에서 생성한 코드가 crate 내부에서도 ::e_drone 경로를 사용할 수 있도록 함
+extern crate self as e_drone;
+
 
 pub mod communication;
 pub mod file;
 pub mod protocol;
 pub mod system;
 
+#[cfg(feature = "tokio")]
+pub mod client;
+
 
 use std::{thread};
-use std::time::{Duration, Instant};
+use std::io;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
 
 use communication::{*};
+use communication::clock::{*};
+use communication::frame::{*};
 use communication::receiver::{*};
+use communication::transport::{*};
 use system::{*};
 use protocol::{*};
 use protocol::display::{*};
 use protocol::command::{*};
 
 
-pub struct Drone
+pub struct Drone<T: Transport = NoTransport>
 {
-    pub time_start: Instant,        // 인스턴스 시작 시각
-    pub time_transfer: Instant,     // 데이터 전송 시각
-    pub time_receive: Instant,      // 데이터 수신 시각
+    pub clock: Arc<dyn Clock>,      // 시계
+    pub time_start: Duration,       // 인스턴스 시작 시각
+    pub time_transfer: Duration,    // 데이터 전송 시각
+    pub time_receive: Duration,     // 데이터 수신 시각
+    pub transport: T,               // 데이터 송수신 통로
     pub receiver: Receiver,         // 데이터 수신 처리기
-    pub header: Header,             // 수신 받은 데이터의 헤더
-    pub vec_data: Vec<u8>,          // 수신 받은 데이터 배열
-    pub data: Data,                 // 수신 받은 데이터 파싱 결과물
+    pub queue_frame: VecDeque<Frame>,   // 응답을 기다리는 동안 수신한 다른 데이터
+    pub queue_expanded: VecDeque<Frame>,    // 데이터 모음을 나누어 만든 개별 데이터
+    pub error_transfer: Option<io::Error>,  // 마지막 전송 실패 원인
+    pub retry_policy: transfer::RetryPolicy,    // 재전송 설정
     pub flag_show_debug_message: bool,  // 디버깅 정보 표시
+    pub flag_expand_assembled: bool,    // 데이터 모음(InformationAssembled)을 받으면 개별 데이터도 함께 전달
+    pub profile: Profile,               // 0xE0 ~ 0xE2 해석 기준
 }
 
 
 impl Drone {
     pub fn new() -> Drone{
+        Drone::with_transport(NoTransport)
+    }
+}
+
+
+#[cfg(feature = "serial")]
+impl Drone<serial::Serial> {
+    pub fn open_serial(path: &str) -> io::Result<Drone<serial::Serial>>{
+        Ok(Drone::with_transport(serial::Serial::open(path)?))
+    }
+}
+
+
+impl<T: Transport> Drone<T> {
+    pub fn with_transport(transport: T) -> Drone<T>{
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock::new());
+        let time_now = clock.now();
+
         Drone{
-            time_start: Instant::now(),
-            time_transfer: Instant::now(),
-            time_receive: Instant::now(),
-            receiver: Receiver::new(),
-            header: Header::new(),
-            vec_data: Vec::new(),
-            data: Data::None,
+            clock: clock.clone(),
+            time_start: time_now,
+            time_transfer: time_now,
+            time_receive: time_now,
+            transport,
+            receiver: Receiver::with_clock(clock),
+            queue_frame: VecDeque::new(),
+            queue_expanded: VecDeque::new(),
+            error_transfer: None,
+            retry_policy: transfer::RetryPolicy::new(),
             flag_show_debug_message: false,
+            flag_expand_assembled: false,
+            profile: Profile::default(),
         }
     }
 
 
+    // 통로를 다시 연결하고 수신 처리기를 초기화
+    pub fn reconnect(&mut self) -> io::Result<()>
+    {
+        self.transport.reconnect()?;
+        self.receiver.clear_all();
+
+        Ok(())
+    }
+
+
     pub fn set_show_debug_message(&mut self, flag_show_debug_message: bool)
     {
         self.flag_show_debug_message = flag_show_debug_message;
@@ -55,6 +105,34 @@ impl Drone {
     }
 
 
+    // 데이터 모음을 받으면 그 프레임 다음에 State, Attitude 등 개별 데이터 프레임을 이어서 전달
+    // 개별 데이터만 처리하는 코드를 그대로 사용할 수 있음
+    pub fn set_expand_assembled(&mut self, flag_expand_assembled: bool)
+    {
+        self.flag_expand_assembled = flag_expand_assembled;
+    }
+
+
+    // 연결한 장치 계열에 맞게 0xE0 ~ 0xE2 해석 기준을 지정
+    // check()로 받은 프레임은 frame.to_data_with_profile(drone.profile)로 해석
+    pub fn set_profile(&mut self, profile: Profile)
+    {
+        self.profile = profile;
+    }
+
+
+    // 시계 교체 (수신 처리기도 같은 시계를 사용)
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>)
+    {
+        self.time_start = clock.now();
+        self.time_transfer = self.time_start;
+        self.time_receive = self.time_start;
+
+        self.receiver.set_clock(clock.clone());
+        self.clock = clock;
+    }
+
+
     pub fn push(&mut self, b: u8)
     {
         if self.flag_show_debug_message 
@@ -81,37 +159,302 @@ impl Drone {
     }
 
 
-    pub fn check(&mut self) -> bool
+    // 통로에서 읽을 수 있는 데이터를 읽어 수신 처리기에 넣음. 읽은 바이트 수를 반환
+    pub fn poll(&mut self) -> io::Result<usize>
+    {
+        let mut buffer = [0u8; 1024];
+
+        match self.transport.read(&mut buffer) {
+            Ok(length) => {
+                self.push_slice(&buffer[..length]);
+                Ok(length)
+            },
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+
+    // 통로로 데이터를 전송. 전송에 실패한 경우 error_transfer에 원인을 남김
+    fn transfer_data(&mut self, vec_data: Vec<u8>) -> Vec<u8>
+    {
+        if let Err(e) = self.write(&vec_data)
+        {
+            if self.flag_show_debug_message 
+            {
+                println!("TX Error: {:?}", e);
+            }
+
+            self.error_transfer = Some(e);
+        }
+
+        vec_data
+    }
+
+
+    pub fn take_error_transfer(&mut self) -> Option<io::Error>
+    {
+        self.error_transfer.take()
+    }
+
+
+    // 수신 완료한 데이터가 있으면 반환. 데이터 해석은 Frame::to_data()
+    pub fn check(&mut self) -> Option<Frame>
+    {
+        // 응답을 기다리는 동안 쌓인 데이터를 먼저 처리
+        if let Some(frame) = self.queue_frame.pop_front()
+        {
+            return Some(frame);
+        }
+
+        self.check_receiver()
+    }
+
+
+    fn check_receiver(&mut self) -> Option<Frame>
     {
+        if let Some(frame) = self.queue_expanded.pop_front()
+        {
+            return Some(frame);
+        }
+
         if let messaging::State::Loaded = self.receiver.check()
         {
             self.receiver.clear();
-            self.time_receive = Instant::now();
+            self.time_receive = self.clock.now();
+
+            let frame = self.receiver.get_frame();
+
+            if self.flag_expand_assembled
+            {
+                self.expand(&frame);
+            }
+
+            return Some(frame);
+        }
+
+        None
+    }
+
+
+    // 데이터 모음을 개별 데이터 프레임으로 나누어 queue_expanded에 추가
+    fn expand(&mut self, frame: &Frame)
+    {
+        if let Ok(data) = frame.to_data_with_profile(self.profile)
+        {
+            if let Some(vec_data) = data.expand()
+            {
+                for data in vec_data.iter()
+                {
+                    if let Some(frame_expanded) = data.to_frame(frame.header.from, frame.header.to)
+                    {
+                        self.queue_expanded.push_back(frame_expanded);
+                    }
+                }
+            }
+        }
+    }
+
+
+    // 조건에 맞는 데이터를 받을 때까지 대기
+    // 대기 시간은 clock 기준이므로 ManualClock을 사용하면 시간을 직접 진행시켜야 함
+    // 그 사이에 수신한 다른 데이터는 queue_frame에 넣어두었다가 check()에서 순서대로 꺼냄
+    // 앞서 다른 데이터를 기다리는 동안 queue_frame에 넣어둔 데이터부터 확인
+    fn wait<F>(&mut self, timeout: Duration, mut f: F) -> io::Result<bool>
+    where
+        F: FnMut(&Header, &Data) -> bool,
+    {
+        let profile = self.profile;
+        let index = self.queue_frame.iter().position(|frame| {
+            match frame.to_data_with_profile(profile) {
+                Ok(data) => f(&frame.header, &data),
+                Err(_) => false,
+            }
+        });
+
+        if let Some(index) = index
+        {
+            self.queue_frame.remove(index);
+            return Ok(true);
+        }
+
+        let time_wait = self.clock.now();
+
+        loop
+        {
+            let length = self.poll()?;
+
+            while let Some(frame) = self.check_receiver()
+            {
+                if let Ok(data) = frame.to_data_with_profile(self.profile)
+                {
+                    if f(&frame.header, &data)
+                    {
+                        return Ok(true);
+                    }
+                }
+
+                self.queue_frame.push_back(frame);
+            }
+
+            if self.clock.now().saturating_sub(time_wait) > timeout
+            {
+                return Ok(false);
+            }
 
-            self.header = self.receiver.get_header().clone();
-            self.vec_data = self.receiver.get_data().clone();
-            self.data = handler::check(&self.header, &self.vec_data);
+            if length == 0
+            {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
 
-            return true;
+
+    fn write(&mut self, vec_data: &[u8]) -> io::Result<()>
+    {
+        if self.flag_show_debug_message 
+        {
+            println!("TX: {:X?}", vec_data);
         }
 
-        return false;
+        self.transport.write(vec_data)?;
+        self.time_transfer = self.clock.now();
+
+        Ok(())
+    }
+
+
+    // 데이터를 요청하고 응답을 받을 때까지 대기
+    pub fn request_and_wait<R: Response>(&mut self, target: DeviceType, timeout: Duration) -> io::Result<R>
+    {
+        self.write(&transfer::request(target, R::DATA_TYPE))?;
+
+        let mut response = None;
+        self.wait(timeout, |header, data| {
+            if header.data_type == R::DATA_TYPE && header.from == target
+            {
+                response = R::from_data(data);
+            }
+            response.is_some()
+        })?;
+
+        match response {
+            Some(response) => Ok(response),
+            None => Err(io::Error::new(io::ErrorKind::TimedOut, format!("No response for {:?} from {:?}", R::DATA_TYPE, target))),
+        }
+    }
+
+
+    // 펌웨어 업데이트를 끝까지 진행하고 마지막 상태(Complete 또는 Failed)를 반환
+    // 진행 중 받은 다른 데이터는 버리며, callback은 진행 상황이 바뀔 때마다 호출
+    pub fn update_firmware<F>(&mut self, session: &mut update::UpdateSession, mut callback: F) -> io::Result<update::State>
+    where
+        F: FnMut(&update::Progress),
+    {
+        let mut action = session.start(self.clock.now());
+        let mut progress = session.get_progress();
+
+        loop
+        {
+            match action {
+                update::Action::Transfer(vec_data) => self.write(&vec_data)?,
+                update::Action::Reconnect => {
+                    self.reconnect()?;
+                    self.queue_frame.clear();
+                    self.queue_expanded.clear();
+
+                    action = session.reconnected(self.clock.now());
+                    continue;
+                },
+                update::Action::None => {},
+            }
+
+            if session.get_progress() != progress
+            {
+                progress = session.get_progress();
+                callback(&progress);
+            }
+
+            if session.is_finished()
+            {
+                return Ok(session.get_state());
+            }
+
+            let length = self.poll()?;
+
+            action = match self.check() {
+                Some(frame) => match frame.to_data_with_profile(self.profile) {
+                    Ok(data) => session.push(&frame.header, &data, self.clock.now()),
+                    Err(_) => update::Action::None,
+                },
+                None => {
+                    if length == 0
+                    {
+                        thread::sleep(Duration::from_millis(1));
+                    }
+
+                    session.check(self.clock.now())
+                },
+            };
+        }
+    }
+
+
+    // 데이터를 전송하고 같은 CRC16을 담은 Ack를 받을 때까지 재전송 (Command, LightDefault, Trim 등 설정 데이터)
+    // 예) drone.transfer_reliable(&transfer::trim(0, 0, 0, 0))
+    pub fn transfer_reliable(&mut self, vec_data: &[u8]) -> io::Result<Ack>
+    {
+        let frame = Frame::decode(vec_data).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let data_type = frame.header.data_type;
+        let target = frame.header.to;
+        let crc16 = frame.crc;
+
+        let retry_policy = self.retry_policy;
+        for index_try in 0..retry_policy.count_try
+        {
+            self.write(vec_data)?;
+
+            let mut ack = None;
+            self.wait(retry_policy.get_timeout(index_try), |header, data| {
+                if let Data::Ack(data) = data
+                {
+                    if header.from == target && data.data_type == data_type && data.crc16 == crc16
+                    {
+                        ack = Some(*data);
+                    }
+                }
+                ack.is_some()
+            })?;
+
+            if let Some(ack) = ack
+            {
+                return Ok(ack);
+            }
+
+            if self.flag_show_debug_message 
+            {
+                println!("No Ack for {:?} / try: {}", data_type, index_try + 1);
+            }
+        }
+
+        Err(io::Error::new(io::ErrorKind::TimedOut, format!("No ack for {:?} from {:?} after {} tries", data_type, target, retry_policy.count_try)))
     }
 
 
     pub fn get_time_passed_from_start(&self) -> u128
     {
-        self.time_start.elapsed().as_millis()
+        self.clock.now().saturating_sub(self.time_start).as_millis()
     }
 
     pub fn get_time_passed_from_last_transfer(&self) -> u128
     {
-        self.time_transfer.elapsed().as_millis()
+        self.clock.now().saturating_sub(self.time_transfer).as_millis()
     }
 
     pub fn get_time_passed_from_last_receive(&self) -> u128
     {
-        self.time_receive.elapsed().as_millis()
+        self.clock.now().saturating_sub(self.time_receive).as_millis()
     }
 
 
@@ -123,24 +466,52 @@ impl Drone {
     }
 
 
+    // -- Send ----------------------------------------------------------------------------------------------
+    // Payload에 연결된 DataType으로 드론에 전송
+    pub fn send<P: Payload>(&mut self, data: &P) -> Vec<u8>
+    {
+        self.send_to(DeviceType::Drone, data)
+    }
+
+    pub fn send_to<P: Payload>(&mut self, target: DeviceType, data: &P) -> Vec<u8>
+    {
+        self.transfer_data(transfer::build(DeviceType::Base, target, data))
+    }
+
+
     // -- Request ----------------------------------------------------------------------------------------------
     pub fn request(&mut self, target: DeviceType, data_type: DataType) -> Vec<u8>
     {
-        transfer::transfer(DataType::Request, DeviceType::Base, target, &Request{data_type}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &Request{data_type}))
+    }
+
+    pub fn request_system_count(&mut self, target: DeviceType, timeout: Duration) -> io::Result<SystemCount>
+    {
+        self.request_and_wait(target, timeout)
+    }
+
+    pub fn request_registration(&mut self, target: DeviceType, timeout: Duration) -> io::Result<Registration>
+    {
+        self.request_and_wait(target, timeout)
+    }
+
+    pub fn request_altitude(&mut self, timeout: Duration) -> io::Result<sensor::Altitude>
+    {
+        self.request_and_wait(DeviceType::Drone, timeout)
     }
 
 
     // -- Command ----------------------------------------------------------------------------------------------
     pub fn command(&mut self, target: DeviceType, command_type: CommandType, option: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::Command, DeviceType::Base, target, &Command{command_type, option}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &Command{command_type, option}))
     }
 
 
     // -- FlightEvent ----------------------------------------------------------------------------------------------
     pub fn flight_event(&mut self, event: FlightEvent) -> Vec<u8>
     {
-        transfer::transfer(DataType::Command, DeviceType::Base, DeviceType::Drone, &Command{command_type: CommandType::FlightEvent, option: event.into()}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Drone, &Command{command_type: CommandType::FlightEvent, option: event.into()}))
     }
 
     pub fn takeoff(&mut self) -> Vec<u8>
@@ -187,174 +558,212 @@ impl Drone {
 
     pub fn trim(&mut self, roll: i16, pitch: i16, yaw: i16, throttle: i16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Trim, DeviceType::Base, DeviceType::Drone, &sensor::Trim{roll, pitch, yaw, throttle}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Drone, &sensor::Trim{roll, pitch, yaw, throttle}))
+    }
+
+    pub fn set_weight(&mut self, weight: f32) -> Vec<u8>
+    {
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Drone, &sensor::Weight{weight}))
+    }
+
+
+    // -- Echo ----------------------------------------------------------------------------------------------
+    pub fn echo(&mut self, target: DeviceType, slice_data: &[u8]) -> Vec<u8>
+    {
+        self.transfer_data(transfer::build(DeviceType::Base, target, &Echo{vec_data: slice_data.to_vec()}))
     }
 
 
     // -- Control ----------------------------------------------------------------------------------------------
     pub fn control(&mut self, roll: i8, pitch: i8, yaw: i8, throttle: i8) -> Vec<u8>
     {
-        transfer::transfer(DataType::Control, DeviceType::Base, DeviceType::Drone, &control::Quad8{roll, pitch, yaw, throttle}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Drone, &control::Quad8{roll, pitch, yaw, throttle}))
     }
 
     pub fn control_request(&mut self, roll: i8, pitch: i8, yaw: i8, throttle: i8, data_type: DataType) -> Vec<u8>
     {
-        transfer::transfer(DataType::Control, DeviceType::Base, DeviceType::Drone, &control::Quad8AndRequestData{roll, pitch, yaw, throttle, data_type}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Drone, &control::Quad8AndRequestData{roll, pitch, yaw, throttle, data_type}))
     }
 
     pub fn control_position(&mut self, x: f32, y: f32, z: f32, velocity: f32, heading: i16, rotational_velocity: i16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Control, DeviceType::Base, DeviceType::Drone, &control::Position{x, y, z, velocity, heading, rotational_velocity}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Drone, &control::Position{x, y, z, velocity, heading, rotational_velocity}))
     }
 
 
     // -- Battle ----------------------------------------------------------------------------------------------
     pub fn battle_ir_message(&mut self, ir_message: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::Battle, DeviceType::Base, DeviceType::Drone, &battle::IrMessage{ir_message}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Drone, &battle::IrMessage{ir_message}))
     }
 
     pub fn battle_light_event_command(&mut self, target:DeviceType, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8, command_type: command::CommandType, option: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::Battle, DeviceType::Base, target, &battle::LightEventCommand{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &battle::LightEventCommand{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}}))
     }
 
     pub fn battle_ir_message_light_event_command(&mut self, target:DeviceType, ir_message: u8, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8, command_type: command::CommandType, option: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::Battle, DeviceType::Base, target, &battle::IrMessageLightEventCommand{ir_message, event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &battle::IrMessageLightEventCommand{ir_message, event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}, command: command::Command{command_type, option}}))
     }
 
 
     // -- Light ----------------------------------------------------------------------------------------------
     pub fn light_manual(&mut self, target:DeviceType, flags: u16, brightness: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::LightManual, DeviceType::Base, target, &light::Manual{flags, brightness}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &light::Manual{flags, brightness}))
     }
 
     pub fn light_mode(&mut self, target:DeviceType, mode: u8, interval: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::LightMode, DeviceType::Base, target, &light::Mode{mode, interval}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &light::Mode{mode, interval}))
     }
 
     pub fn light_event(&mut self, target:DeviceType, event: u8, interval: u16, repeat: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::LightEvent, DeviceType::Base, target, &light::Event{event, interval, repeat}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &light::Event{event, interval, repeat}))
     }
 
     pub fn light_mode_color(&mut self, target:DeviceType, mode: u8, interval: u16, r: u8, g: u8, b: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::LightMode, DeviceType::Base, target, &light::ModeColor{mode:light::Mode{mode, interval}, color: light::Color{r, g, b}}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &light::ModeColor{mode:light::Mode{mode, interval}, color: light::Color{r, g, b}}))
     }
 
     pub fn light_event_color(&mut self, target:DeviceType, event: u8, interval: u16, repeat: u8, r: u8, g: u8, b: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::LightEvent, DeviceType::Base, target, &light::EventColor{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &light::EventColor{event:light::Event{event, interval, repeat}, color: light::Color{r, g, b}}))
     }
 
     pub fn light_default(&mut self, target:DeviceType, mode: u8, interval: u16, r: u8, g: u8, b: u8) -> Vec<u8>
     {
-        transfer::transfer(DataType::LightDefault, DeviceType::Base, target, &light::ModeColor{mode:light::Mode{mode, interval}, color: light::Color{r, g, b}}.to_vec())
+        self.transfer_data(transfer::transfer(DataType::LightDefault, DeviceType::Base, target, &light::ModeColor{mode:light::Mode{mode, interval}, color: light::Color{r, g, b}}.to_vec()))
     }
 
 
     // -- Buzzer ----------------------------------------------------------------------------------------------
     pub fn buzzer_stop(&mut self, target: DeviceType) -> Vec<u8>
     {
-        transfer::transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::Stop, hz:0, time:0}.to_vec())
+        self.transfer_data(transfer::buzzer_stop(target))
     }
 
     pub fn buzzer_scale(&mut self, target: DeviceType, scale: buzzer::Scale, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleInstantly, scale, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleInstantly, scale, time}))
     }
 
     pub fn buzzer_scale_reserve(&mut self, target: DeviceType, scale: buzzer::Scale, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleContinually, scale, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &buzzer::BuzzerScale{mode: buzzer::Mode::ScaleContinually, scale, time}))
     }
 
     pub fn buzzer_hz(&mut self, target: DeviceType, hz: u16, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzInstantly, hz, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzInstantly, hz, time}))
     }
 
     pub fn buzzer_hz_reserve(&mut self, target: DeviceType, hz: u16, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzContinually, hz, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::HzContinually, hz, time}))
     }
 
     pub fn buzzer_mute(&mut self, target: DeviceType, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteInstantly, hz: 0, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteInstantly, hz: 0, time}))
     }
 
     pub fn buzzer_mute_reserve(&mut self, target: DeviceType, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Buzzer, DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteContinually, hz: 0, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, target, &buzzer::BuzzerHz{mode: buzzer::Mode::MuteContinually, hz: 0, time}))
     }
 
 
     // -- Vibrator ----------------------------------------------------------------------------------------------
     pub fn vibrator(&mut self, on: u16, off: u16, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Vibrator, DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Instantly, on, off, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Instantly, on, off, time}))
     }
 
     pub fn vibrator_reserve(&mut self, on: u16, off: u16, time: u16) -> Vec<u8>
     {
-        transfer::transfer(DataType::Vibrator, DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Continually, on, off, time}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &vibrator::Vibrator{mode: vibrator::Mode::Continually, on, off, time}))
     }
 
 
     // -- Display ----------------------------------------------------------------------------------------------
     pub fn draw_clear_all(&mut self, pixel: Pixel) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayClear, DeviceType::Base, DeviceType::Controller, &ClearAll{pixel}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &ClearAll{pixel}))
     }
 
     pub fn draw_clear(&mut self, x: i16, y: i16, width: i16, height: i16, pixel: Pixel) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayClear, DeviceType::Base, DeviceType::Controller, &Clear{x, y, width, height, pixel}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &Clear{x, y, width, height, pixel}))
     }
 
     pub fn draw_invert(&mut self, x: i16, y: i16, width: i16, height: i16) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayInvert, DeviceType::Base, DeviceType::Controller, &Invert{x, y, width, height}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &Invert{x, y, width, height}))
     }
 
     pub fn draw_point(&mut self, x: i16, y: i16, pixel: Pixel) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayDrawPoint, DeviceType::Base, DeviceType::Controller, &DrawPoint{x, y, pixel}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &DrawPoint{x, y, pixel}))
     }
 
     pub fn draw_line(&mut self, x1: i16, y1: i16, x2: i16, y2: i16, pixel: Pixel, line: Line) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayDrawLine, DeviceType::Base, DeviceType::Controller, &DrawLine{x1, y1, x2, y2, pixel, line}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &DrawLine{x1, y1, x2, y2, pixel, line}))
     }
 
     pub fn draw_rect(&mut self, x: i16, y: i16, width: i16, height: i16, pixel: Pixel, fill: bool, line: Line) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayDrawRect, DeviceType::Base, DeviceType::Controller, &DrawRect{x, y, width, height, pixel, fill, line}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &DrawRect{x, y, width, height, pixel, fill, line}))
     }
 
     pub fn draw_circle(&mut self, x: i16, y: i16, radius: i16, pixel: Pixel, fill: bool) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayDrawCircle, DeviceType::Base, DeviceType::Controller, &DrawCircle{x, y, radius, pixel, fill}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &DrawCircle{x, y, radius, pixel, fill}))
     }
 
     pub fn draw_string(&mut self, x: i16, y: i16, font: Font, pixel: Pixel, string: String) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayDrawString, DeviceType::Base, DeviceType::Controller, &DrawString{x, y, font, pixel, string}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &DrawString{x, y, font, pixel, string}))
     }
 
     pub fn draw_string_align(&mut self, x_start: i16, x_end: i16, y: i16, align: Align, font: Font, pixel: Pixel, string: String) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayDrawStringAlign, DeviceType::Base, DeviceType::Controller, &DrawStringAlign{x_start, x_end, y, align, font, pixel, string}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &DrawStringAlign{x_start, x_end, y, align, font, pixel, string}))
     }
 
     pub fn draw_image(&mut self, x: i16, y: i16, width: i16, height: i16, vec_image: Vec<u8>) -> Vec<u8>
     {
-        transfer::transfer(DataType::DisplayDrawImage, DeviceType::Base, DeviceType::Controller, &DrawImage{x, y, width, height, vec_image}.to_vec())
+        self.transfer_data(transfer::build(DeviceType::Base, DeviceType::Controller, &DrawImage{x, y, width, height, vec_image}))
+    }
+
+
+    // -- GpsRtk ----------------------------------------------------------------------------------------------
+    // 이동국 상태는 request_and_wait::<navigation::RtkNavigationState>로 확인
+    pub fn gps_rtk_base(&mut self, enable: bool) -> Vec<u8>
+    {
+        self.transfer_data(transfer::gps_rtk_base(enable))
+    }
+
+    pub fn gps_rtk_rover(&mut self, enable: bool) -> Vec<u8>
+    {
+        self.transfer_data(transfer::gps_rtk_rover(enable))
+    }
+
+
+    // -- External ----------------------------------------------------------------------------------------------
+    // 상태를 받으려면 set_profile(Profile::External)로 설정
+    pub fn external_system_command(&mut self, target: DeviceType, command_type: external::system::CommandType) -> Vec<u8>
+    {
+        self.transfer_data(transfer::external_system_command(target, command_type))
+    }
+
+    pub fn external_camera_command(&mut self, target: DeviceType, command_type: external::camera::CommandType) -> Vec<u8>
+    {
+        self.transfer_data(transfer::external_camera_command(target, command_type))
     }
 }
 
@@ -362,8 +771,228 @@ impl Drone {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn loopback() {
+        let mut drone = Drone::with_transport(Loopback::new());
+
+        let vec_data = drone.takeoff();
+        assert_eq!(drone.poll().unwrap(), vec_data.len());
+
+        let frame = drone.check().unwrap();
+        assert_eq!(frame.encode(), vec_data);
+        assert_eq!(frame.header.data_type, DataType::Command);
+        assert_eq!(frame.header.to, DeviceType::Drone);
+
+        match frame.to_data().unwrap() {
+            Data::Command(command) => {
+                assert_eq!(command.command_type, CommandType::FlightEvent);
+                assert_eq!(command.option, u8::from(FlightEvent::Takeoff));
+            },
+            data => panic!("unexpected data: {:?}", data),
+        }
+    }
+
+    #[test]
+    fn send() {
+        let mut drone = Drone::with_transport(Loopback::new());
+
+        let lost_connection = protocol::communication::LostConnection{time_neutral: 1000, time_landing: 2000, time_stop: 3000};
+        let vec_data = drone.send(&lost_connection);
+        assert_eq!(vec_data, transfer::transfer(DataType::LostConnection, DeviceType::Base, DeviceType::Drone, &lost_connection.to_vec()));
+
+        let vec_data = drone.send_to(DeviceType::Controller, &navigation::Target::new());
+        drone.poll().unwrap();
+        drone.check().unwrap();
+
+        let frame = drone.check().unwrap();
+        assert_eq!(frame.encode(), vec_data);
+        assert_eq!(frame.header.data_type, DataType::NavigationTarget);
+        assert_eq!(frame.header.to, DeviceType::Controller);
+    }
+
+    #[test]
+    fn request_and_wait() {
+        let mut drone = Drone::with_transport(Loopback::new());
+
+        // 요청 전에 도착해 있던 다른 데이터
+        let vec_controller = transfer::transfer(DataType::Attitude, DeviceType::Controller, DeviceType::Base, &sensor::Attitude{roll: 1, pitch: 2, yaw: 3}.to_vec());
+        let vec_attitude = transfer::transfer(DataType::Attitude, DeviceType::Drone, DeviceType::Base, &sensor::Attitude{roll: 10, pitch: 20, yaw: 30}.to_vec());
+        drone.transport.queue_buffer.extend(&vec_controller);
+        drone.transport.queue_buffer.extend(&vec_attitude);
+
+        let attitude = drone.request_and_wait::<sensor::Attitude>(DeviceType::Drone, Duration::from_millis(100)).unwrap();
+        assert_eq!((attitude.roll, attitude.pitch, attitude.yaw), (10, 20, 30));
+
+        // 조종기에서 온 Attitude는 버리지 않고 보관
+        let frame = drone.check().unwrap();
+        assert_eq!(frame.header.from, DeviceType::Controller);
+        assert!(matches!(frame.to_data(), Ok(Data::Attitude(_))));
+
+        // 루프백으로 되돌아온 요청 데이터
+        assert!(matches!(drone.check().unwrap().to_data(), Ok(Data::Request(_))));
+        assert!(drone.check().is_none());
+
+        // 응답이 없는 경우
+        let error = drone.request_and_wait::<sensor::Motion>(DeviceType::Drone, Duration::from_millis(10)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn request_and_wait_reversed() {
+        let mut drone = Drone::with_transport(Loopback::new());
+
+        // 두 요청의 응답이 요청과 반대 순서로 도착
+        drone.request(DeviceType::Drone, DataType::Motion);
+        drone.request(DeviceType::Drone, DataType::Attitude);
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Drone, DeviceType::Base, &sensor::Attitude{roll: 10, pitch: 20, yaw: 30}));
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Drone, DeviceType::Base, &sensor::Motion::new()));
+
+        let motion = drone.request_and_wait::<sensor::Motion>(DeviceType::Drone, Duration::from_millis(100)).unwrap();
+        assert_eq!(motion, sensor::Motion::new());
+
+        // Motion을 기다리는 동안 받아둔 Attitude를 사용
+        drone.transport.queue_buffer.clear();
+        let attitude = drone.request_and_wait::<sensor::Attitude>(DeviceType::Drone, Duration::from_millis(10)).unwrap();
+        assert_eq!(attitude, sensor::Attitude{roll: 10, pitch: 20, yaw: 30});
+
+        // 꺼낸 응답은 check()에서 다시 나오지 않음
+        while let Some(frame) = drone.check() {
+            assert!(matches!(frame.to_data(), Ok(Data::Request(_))));
+        }
+    }
+
+    #[test]
+    fn request_helpers() {
+        let mut drone = Drone::with_transport(Loopback::new());
+
+        let altitude = sensor::Altitude{temperature: 25.5, pressure: 101325.0, altitude: 12.5, range_height: 0.8};
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Drone, DeviceType::Base, &altitude));
+        assert_eq!(drone.request_altitude(Duration::from_millis(100)).unwrap(), altitude);
+
+        let system_count = SystemCount{time_system: 60000, count_reset: 3, count_error: 1};
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Controller, DeviceType::Base, &system_count));
+        assert_eq!(drone.request_system_count(DeviceType::Controller, Duration::from_millis(100)).unwrap(), system_count);
+
+        let registration = Registration{vec_data: (0..20).collect()};
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Drone, DeviceType::Base, &registration));
+        assert_eq!(drone.request_registration(DeviceType::Drone, Duration::from_millis(100)).unwrap(), registration);
+    }
+
+    #[test]
+    fn gps_rtk() {
+        let mut drone = Drone::with_transport(Loopback::new());
+
+        assert_eq!(drone.gps_rtk_rover(true), transfer::command(DeviceType::Drone, CommandType::GpsRtkRover, 1));
+        drone.poll().unwrap();
+        assert!(matches!(drone.check().unwrap().to_data(), Ok(Data::Command(Command{command_type: CommandType::GpsRtkRover, option: 1}))));
+
+        // UBX-NAV-RELPOSNED, UBX-RXM-RAWX와 같은 크기
+        assert_eq!(navigation::RtkNavigationState::size(), 64);
+        assert_eq!(navigation::RtkExtendedRawMeasurementData::size(), 16);
+        assert_eq!(navigation::RtkRawMeasurement::size(), 32);
+
+        // 기준국과 RTK 고정해(Fixed)를 얻은 이동국 상태
+        let state = navigation::RtkNavigationState{rel_pos_n: 120, rel_pos_hp_n: -7, rel_pos_length: 125, flags: 0x0017, ..navigation::RtkNavigationState::new()};
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Drone, DeviceType::Base, &state));
+
+        let state = drone.request_and_wait::<navigation::RtkNavigationState>(DeviceType::Drone, Duration::from_millis(100)).unwrap();
+        assert!(state.is_fix_ok() && state.is_relative_position_valid());
+        assert_eq!(state.get_carrier_solution(), navigation::CarrierSolution::Fixed);
+        assert_eq!(state.rel_pos_length, 125);
+        assert!((state.get_rel_pos_ned().0 - 1.1993).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expand_assembled() {
+        let mut drone = Drone::with_transport(Loopback::new());
+        drone.set_expand_assembled(true);
+
+        let entry = assembled::ForEntry{angle_roll: 4, angle_pitch: -6, angle_yaw: 90, position_x: -10, position_y: 20, position_z: 150, range_height: 812, altitude: 45.5};
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Drone, DeviceType::Base, &entry));
+        drone.poll().unwrap();
+
+        // 데이터 모음 다음에 개별 데이터가 이어짐
+        assert!(matches!(drone.check().unwrap().to_data(), Ok(Data::InformationAssembledForEntry(_))));
+
+        let frame = drone.check().unwrap();
+        assert_eq!(frame.header.from, DeviceType::Drone);
+        assert_eq!(frame.to_data(), Ok(Data::Attitude(sensor::Attitude{roll: 4, pitch: -6, yaw: 90})));
+        assert_eq!(drone.check().unwrap().to_data(), Ok(Data::Position(sensor::Position{x: -0.1, y: 0.2, z: 1.5})));
+        assert!(drone.check().is_none());
+
+        // 개별 데이터를 기다리는 요청도 데이터 모음으로 응답 받을 수 있음
+        drone.transport.queue_buffer.extend(&transfer::build(DeviceType::Drone, DeviceType::Base, &entry));
+        let attitude = drone.request_and_wait::<sensor::Attitude>(DeviceType::Drone, Duration::from_millis(100)).unwrap();
+        assert_eq!(attitude.yaw, 90);
+    }
+
+    // 앞의 count_drop개를 버리고 이후 받은 데이터에 Ack로 응답
+    // 받은 데이터가 없으면 clock을 1ms 진행
+    struct LossyLink {
+        count_drop: u32,
+        count_write: u32,
+        loopback: Loopback,
+        clock: Arc<ManualClock>,
+    }
+
+    impl Transport for LossyLink {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let length = self.loopback.read(buffer)?;
+            if length == 0 {
+                self.clock.advance(Duration::from_millis(1));
+            }
+            Ok(length)
+        }
+
+        fn write(&mut self, slice_data: &[u8]) -> io::Result<()> {
+            self.count_write += 1;
+            if self.count_write <= self.count_drop {
+                return Ok(());
+            }
+
+            let length = slice_data.len();
+            let ack = Ack{
+                system_time: 0,
+                data_type: DataType::from_u8(slice_data[2]),
+                crc16: u16::from_le_bytes([slice_data[length - 2], slice_data[length - 1]]),
+            };
+            let from = DeviceType::from_u8(slice_data[5]);
+            self.loopback.write(&transfer::transfer(DataType::Ack, from, DeviceType::Base, &ack.to_vec()))
+        }
+    }
+
+    #[test]
+    fn transfer_reliable() {
+        let clock = Arc::new(ManualClock::new());
+        let mut drone = Drone::with_transport(LossyLink{count_drop: 2, count_write: 0, loopback: Loopback::new(), clock: clock.clone()});
+        drone.set_clock(clock.clone());
+        clock.advance(Duration::from_millis(500));
+        drone.retry_policy.time_timeout = Duration::from_millis(2);
+
+        // 대기 시간 2ms, 4ms를 넘긴 시점(3ms, 5ms)에 다시 전송
+        let vec_data = transfer::trim(1, 2, 3, 4);
+        let ack = drone.transfer_reliable(&vec_data).unwrap();
+        assert_eq!(ack.data_type, DataType::Trim);
+        assert_eq!(drone.transport.count_write, 3);
+        assert_eq!(drone.get_time_passed_from_last_transfer(), 0);
+        assert_eq!(drone.get_time_passed_from_last_receive(), 0);
+        assert_eq!(drone.get_time_passed_from_start(), 508);
+
+        // 다른 데이터의 Ack는 인정하지 않음
+        drone.transport.loopback.write(&transfer::transfer(DataType::Ack, DeviceType::Drone, DeviceType::Base, &Ack{system_time: 0, data_type: DataType::Command, crc16: 0}.to_vec())).unwrap();
+        drone.transport.count_drop = 100;
+        drone.retry_policy.count_try = 3;
+
+        let error = drone.transfer_reliable(&transfer::set_default()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(drone.transport.count_write, 6);
+        assert!(matches!(drone.check().unwrap().to_data(), Ok(Data::Ack(_))));
+    }
 }
@@ -0,0 +1,187 @@
+use crate::protocol::{*};
+use crate::protocol::sensor::{Attitude, Position};
+use crate::communication::extractor::CheckedExtractor;
+use crate::communication::handler;
+use crate::system::DeviceType;
+
+
+// -- ForController -----------------------------------------------------------------------------------------------
+// 조종기 화면 표시용 데이터 모음 (InformationAssembledForController)
+// 배치는 e_drone Python 라이브러리 protocol.py의 InformationAssembledForController('<bbhHhhhbbBb')와 같음
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
+pub struct ForController {
+    pub angle_roll: i8,         // 자세(degree)
+    pub angle_pitch: i8,
+    pub angle_yaw: i16,
+
+    pub rpm: u16,               // 모터 회전수
+
+    pub position_x: i16,        // 위치(cm)
+    pub position_y: i16,
+    pub position_z: i16,
+
+    pub speed_x: i8,            // 속도(0.1 m/s)
+    pub speed_y: i8,
+
+    pub range_height: u8,       // 바닥까지의 거리(cm)
+    pub rssi: i8,               // 신호 세기(dBm)
+}
+
+
+impl ForController {
+    pub fn new() -> ForController{
+        ForController {
+            angle_roll: 0,
+            angle_pitch: 0,
+            angle_yaw: 0,
+            rpm: 0,
+            position_x: 0,
+            position_y: 0,
+            position_z: 0,
+            speed_x: 0,
+            speed_y: 0,
+            range_height: 0,
+            rssi: 0,
+        }
+    }
+
+
+    // 개별 데이터로 나눔 (rpm, speed, range_height는 대응하는 데이터가 없음)
+    pub fn to_vec_data(&self) -> Vec<Data>
+    {
+        vec![
+            Data::Attitude(Attitude{ roll: self.angle_roll as i16, pitch: self.angle_pitch as i16, yaw: self.angle_yaw }),
+            Data::Position(Position{ x: self.position_x as f32 / 100.0, y: self.position_y as f32 / 100.0, z: self.position_z as f32 / 100.0 }),
+            Data::Rssi(communication::Rssi{ rssi: self.rssi }),
+        ]
+    }
+}
+
+
+impl Default for ForController {
+    fn default() -> ForController {
+        ForController::new()
+    }
+}
+
+
+// -- ForEntry -----------------------------------------------------------------------------------------------
+// 입문용 드론 데이터 모음 (InformationAssembledForEntry)
+// 배치는 e_drone Python 라이브러리 protocol.py의 InformationAssembledForEntry('<hhhhhhhf')와 같음
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
+pub struct ForEntry {
+    pub angle_roll: i16,        // 자세(degree)
+    pub angle_pitch: i16,
+    pub angle_yaw: i16,
+
+    pub position_x: i16,        // 위치(cm)
+    pub position_y: i16,
+    pub position_z: i16,
+
+    pub range_height: i16,      // 바닥까지의 거리(mm)
+    pub altitude: f32,          // 고도(m)
+}
+
+
+impl ForEntry {
+    pub fn new() -> ForEntry{
+        ForEntry {
+            angle_roll: 0,
+            angle_pitch: 0,
+            angle_yaw: 0,
+            position_x: 0,
+            position_y: 0,
+            position_z: 0,
+            range_height: 0,
+            altitude: 0.0_f32,
+        }
+    }
+
+
+    // 개별 데이터로 나눔 (range_height, altitude는 대응하는 데이터가 없음)
+    pub fn to_vec_data(&self) -> Vec<Data>
+    {
+        vec![
+            Data::Attitude(Attitude{ roll: self.angle_roll, pitch: self.angle_pitch, yaw: self.angle_yaw }),
+            Data::Position(Position{ x: self.position_x as f32 / 100.0, y: self.position_y as f32 / 100.0, z: self.position_z as f32 / 100.0 }),
+        ]
+    }
+}
+
+
+impl Default for ForEntry {
+    fn default() -> ForEntry {
+        ForEntry::new()
+    }
+}
+
+
+// -- ByBlocks -----------------------------------------------------------------------------------------------
+// 여러 데이터를 블럭 단위로 이어 붙인 데이터 모음 (InformationAssembledForByBlocks)
+// 블럭은 DataType(1), 길이(1), 데이터 순서로 구성
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ByBlocks {
+    pub vec_data: Vec<Data>,
+}
+
+
+impl ByBlocks {
+    pub fn new() -> ByBlocks{
+        ByBlocks {
+            vec_data: Vec::new(),
+        }
+    }
+
+
+    pub const fn size() -> usize { 0 }
+
+
+    pub fn parse(slice_data: &[u8]) -> Result<ByBlocks, ProtocolError> {
+        ByBlocks::parse_with_profile(Profile::default(), slice_data)
+    }
+
+
+    // 블럭 안의 0xE0 ~ 0xE2도 장치 계열에 따라 해석
+    pub fn parse_with_profile(profile: Profile, slice_data: &[u8]) -> Result<ByBlocks, ProtocolError> {
+        let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
+        let mut by_blocks: ByBlocks = ByBlocks::new();
+
+        while ext.remaining() > 0
+        {
+            let data_type: DataType = enum_from_u8("DataType", ext.get_u8()?)?;
+            let length = ext.get_u8()?;
+            let header = Header{ data_type, length, from: DeviceType::None, to: DeviceType::None };
+
+            by_blocks.vec_data.push(handler::check_with_profile(profile, &header, ext.get_slice(length as usize)?)?);
+        }
+
+        Ok(by_blocks)
+    }
+
+
+    // 개별 데이터로 나눔
+    pub fn to_vec_data(&self) -> Vec<Data>
+    {
+        self.vec_data.clone()
+    }
+}
+
+
+impl Serializable for ByBlocks {
+    // 전송할 수 없는 데이터와 블럭 길이(1바이트)를 넘는 데이터는 제외
+    fn to_vec(&self) -> Vec<u8> {
+        let mut vec_data : Vec<u8> = Vec::new();
+
+        for data in self.vec_data.iter() {
+            if let Some(payload) = data.to_payload() {
+                if let Ok(length) = u8::try_from(payload.len()) {
+                    vec_data.push(data.data_type().into());
+                    vec_data.push(length);
+                    vec_data.extend_from_slice(&payload);
+                }
+            }
+        }
+
+        vec_data
+    }
+}
@@ -1,11 +1,11 @@
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 use crate::protocol::light::{*};
 use crate::protocol::command::{*};
 
 
 // -- IrMessage -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct IrMessage {
     pub ir_message: u8,
 }
@@ -22,14 +22,14 @@ impl IrMessage {
     pub const fn size() -> usize { 1 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<IrMessage, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<IrMessage, ProtocolError> {
         if slice_data.len() == IrMessage::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(IrMessage{
-                ir_message: ext.get_u8(),
+                ir_message: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: IrMessage::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -46,7 +46,7 @@ impl Serializable for IrMessage {
 
 
 // -- LightEventColorCommand -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct LightEventCommand {
     pub event: Event,
     pub color: Color,
@@ -67,27 +67,27 @@ impl LightEventCommand {
     pub const fn size() -> usize { 9 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<LightEventCommand, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<LightEventCommand, ProtocolError> {
         if slice_data.len() == LightEventCommand::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok( LightEventCommand{
                 event: Event {
-                    event: ext.get_u8(),
-                    interval: ext.get_u16(),
-                    repeat: ext.get_u8(),
+                    event: ext.get_u8()?,
+                    interval: ext.get_u16()?,
+                    repeat: ext.get_u8()?,
                 },
                 color: Color {
-                    r: ext.get_u8(),
-                    g: ext.get_u8(),
-                    b: ext.get_u8(),
+                    r: ext.get_u8()?,
+                    g: ext.get_u8()?,
+                    b: ext.get_u8()?,
                 },
                 command: Command {
-                    command_type: CommandType::None,
-                    option: 0,
+                    command_type: enum_from_u8("LightEventCommand::command_type", ext.get_u8()?)?,
+                    option: ext.get_u8()?,
                 }
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: LightEventCommand::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -111,7 +111,7 @@ impl Serializable for LightEventCommand {
 
 
 // -- IrMessageLightEventCommand -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct IrMessageLightEventCommand {
     pub ir_message: u8,
     pub event: Event,
@@ -134,28 +134,28 @@ impl IrMessageLightEventCommand {
     pub const fn size() -> usize { 10 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<IrMessageLightEventCommand, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<IrMessageLightEventCommand, ProtocolError> {
         if slice_data.len() == IrMessageLightEventCommand::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok( IrMessageLightEventCommand{
-                ir_message: ext.get_u8(),
+                ir_message: ext.get_u8()?,
                 event: Event {
-                    event: ext.get_u8(),
-                    interval: ext.get_u16(),
-                    repeat: ext.get_u8(),
+                    event: ext.get_u8()?,
+                    interval: ext.get_u16()?,
+                    repeat: ext.get_u8()?,
                 },
                 color: Color {
-                    r: ext.get_u8(),
-                    g: ext.get_u8(),
-                    b: ext.get_u8(),
+                    r: ext.get_u8()?,
+                    g: ext.get_u8()?,
+                    b: ext.get_u8()?,
                 },
                 command: Command {
-                    command_type: CommandType::None,
-                    option: 0,
+                    command_type: enum_from_u8("IrMessageLightEventCommand::command_type", ext.get_u8()?)?,
+                    option: ext.get_u8()?,
                 }
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: IrMessageLightEventCommand::size(), actual: slice_data.len() }) }
     }
 }
 
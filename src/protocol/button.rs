@@ -2,8 +2,8 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- Event ----------------------------------------------------------------------------------------------
@@ -32,7 +32,7 @@ impl Event {
 
 
 // -- Button -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Button {
     pub button: u16,
     pub event: Event,
@@ -51,15 +51,15 @@ impl Button {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Button, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Button, ProtocolError> {
         if slice_data.len() == Button::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Button{
-                button: ext.get_u16(),
-                event: Event::from_u8(ext.get_u8()),
+                button: ext.get_u16()?,
+                event: enum_from_u8("Button::event", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Button::size(), actual: slice_data.len() }) }
     }
 }
 
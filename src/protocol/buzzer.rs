@@ -3,8 +3,8 @@ use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- Mode ----------------------------------------------------------------------------------------------
@@ -72,7 +72,7 @@ impl Scale {
 
 
 // -- Melody -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Melody {
     pub melody: u8,
     pub repeat: u8,
@@ -91,15 +91,15 @@ impl Melody {
     pub const fn size() -> usize { 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Melody, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Melody, ProtocolError> {
         if slice_data.len() == Melody::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Melody{
-                melody: ext.get_u8(),
-                repeat: ext.get_u8(),
+                melody: ext.get_u8()?,
+                repeat: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Melody::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -117,7 +117,7 @@ impl Serializable for Melody {
 
 
 // -- BuzzerScale -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct BuzzerScale {
     pub mode: Mode,
     pub scale: Scale,
@@ -138,16 +138,16 @@ impl BuzzerScale {
     pub const fn size() -> usize { 5 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<BuzzerScale, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<BuzzerScale, ProtocolError> {
         if slice_data.len() == BuzzerScale::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(BuzzerScale{
-                mode: Mode::from_u8(ext.get_u8()),
-                scale: Scale::from_u16(ext.get_u16()),
-                time: ext.get_u16(),
+                mode: enum_from_u8("BuzzerScale::mode", ext.get_u8()?)?,
+                scale: Scale::from_u16(ext.get_u16()?),
+                time: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: BuzzerScale::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -166,7 +166,7 @@ impl Serializable for BuzzerScale {
 
 
 // -- BuzzerHz -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct BuzzerHz {
     pub mode: Mode,
     pub hz: u16,
@@ -187,16 +187,16 @@ impl BuzzerHz {
     pub const fn size() -> usize { 5 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<BuzzerHz, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<BuzzerHz, ProtocolError> {
         if slice_data.len() == BuzzerHz::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(BuzzerHz{
-                mode: Mode::from_u8(ext.get_u8()),
-                hz: ext.get_u16(),
-                time: ext.get_u16(),
+                mode: enum_from_u8("BuzzerHz::mode", ext.get_u8()?)?,
+                hz: ext.get_u16()?,
+                time: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: BuzzerHz::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -2,8 +2,7 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{Protocol, ProtocolError};
 
 
 // -- LightLocation ----------------------------------------------------------------------------------------------
@@ -363,10 +362,12 @@ impl CardColor {
 
 
 // -- Classify -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Protocol)]
 pub struct Classify {
     pub index: i8,
+    #[protocol(array = Classify::END)]
     pub cc: Vec<i8>,
+    #[protocol(array = 2)]
     pub l: Vec<i8>,
 }
 
@@ -427,63 +428,20 @@ impl Classify {
     }
 
 
-    pub const fn size() -> usize { 1 + 6 * 3 * 2 + 2 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Vec<Classify>, &'static str> {
-        if slice_data.len() > 0 && slice_data.len() % Classify::size() == 0 {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            let array_length = slice_data.len() / Classify::size();
-            let mut vec_classify: Vec<Classify> = Vec::new();
-
-            for _i in 0..array_length
-            {
-                let mut cl : Classify = Classify::new();
-                
-                cl.index = ext.get_i8();
-
-                for _j in 0..Classify::END
-                {
-                    cl.cc.push(ext.get_i8());
-                }
-
-                cl.l.push(ext.get_i8());
-                cl.l.push(ext.get_i8());
-
-                vec_classify.push(cl);
-            }
-
-            Ok(vec_classify)
+    // CardClassify는 Classify 여러 개를 이어서 전송
+    pub fn parse_array(slice_data: &[u8]) -> Result<Vec<Classify>, ProtocolError> {
+        if !slice_data.is_empty() && slice_data.len() % Classify::size() == 0 {
+            slice_data.chunks(Classify::size()).map(Classify::parse).collect()
         }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Classify {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.push(self.index as u8);
-        
-        for v in self.cc.iter()
-        {
-            vec_data.push(*v as u8);
-        }
-
-        for v in self.l.iter()
-        {
-            vec_data.push(*v as u8);
-        }
-
-        vec_data
+        else { Err(ProtocolError::LengthMismatch{ expected: Classify::size(), actual: slice_data.len() }) }
     }
 }
 
 
 // -- Range -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Protocol)]
 pub struct Range {
+    #[protocol(array = Range::END)]
     pub range: Vec<i16>,
 }
 
@@ -512,48 +470,19 @@ impl Range {
             range: Vec::new(),
         }
     }
-
-
-    pub const fn size() -> usize { 2 * 12 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Range, &'static str> {
-        if slice_data.len() == Range::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            let mut r : Range = Range::new();
-
-            for _i in 0..Range::END
-            {
-                r.range.push(ext.get_i16());
-            }
-
-            Ok(r)
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Range {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        for v in self.range.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        vec_data
-    }
 }
 
 
 // -- Raw -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Protocol)]
 pub struct Raw {
+    #[protocol(array = 6)]
     pub raw: Vec<i16>,
+    #[protocol(array = 6)]
     pub rgb: Vec<u8>,
+    #[protocol(array = 8)]
     pub hsvl: Vec<i16>,
+    #[protocol(array = 2)]
     pub color: Vec<u8>,
     pub card: u8,
 }
@@ -593,81 +522,15 @@ impl Raw {
             card: 0,
         }
     }
-
-
-    pub const fn size() -> usize { (2 * 2 * 3) + (2 * 3) + (2 * 2 * 4) + 2 + 1 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Raw, &'static str> {
-        if slice_data.len() > 0 && slice_data.len() % Raw::size() == 0 {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-
-            let mut r : Raw = Raw::new();
-
-            for _i in 0..6
-            {
-                r.raw.push(ext.get_i16());
-            }
-
-            for _i in 0..6
-            {
-                r.rgb.push(ext.get_u8());
-            }
-
-            for _i in 0..8
-            {
-                r.hsvl.push(ext.get_i16());
-            }
-
-            for _i in 0..2
-            {
-                r.color.push(ext.get_u8());
-            }
-
-            r.card = ext.get_u8();
-
-            Ok(r)
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Raw {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        for v in self.raw.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        for v in self.rgb.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        for v in self.hsvl.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        for v in self.color.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        vec_data.push(self.card);
-        
-        vec_data
-    }
 }
 
 
 // -- Color -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Protocol)]
 pub struct Color {
+    #[protocol(array = 8)]
     pub hsvl: Vec<i16>,
+    #[protocol(array = 2)]
     pub color: Vec<u8>,
     pub card: u8,
 }
@@ -696,63 +559,16 @@ impl Color {
             card: 0,
         }
     }
-
-
-    pub const fn size() -> usize { (2 * 2 * 4) + 2 + 1 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Color, &'static str> {
-        if slice_data.len() > 0 && slice_data.len() % Color::size() == 0 {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-
-            let mut r : Color = Color::new();
-
-            for _i in 0..8
-            {
-                r.hsvl.push(ext.get_i16());
-            }
-
-            for _i in 0..2
-            {
-                r.color.push(ext.get_u8());
-            }
-
-            r.card = ext.get_u8();
-
-            Ok(r)
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Color {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        for v in self.hsvl.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        for v in self.color.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        vec_data.push(self.card);
-        
-        vec_data
-    }
 }
 
 
 // -- ListCard -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Protocol)]
 pub struct ListCard {
     pub index_run: u8,
     pub total_size: u8,
     pub index: u8,
+    #[protocol(tail)]
     pub card: Vec<u8>,
 }
 
@@ -767,59 +583,19 @@ impl ListCard {
     }
 
 
-    pub const fn size() -> usize { 1 + 1 + 1 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<ListCard, &'static str> {
-        if slice_data.len() == ListCard::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            let mut lc : ListCard = ListCard::new();
-
-            lc.index_run = ext.get_u8();
-            lc.total_size = ext.get_u8();
-            lc.index = ext.get_u8();
-
-            for _i in 0..(slice_data.len() - ListCard::size())
-            {
-                lc.card.push(ext.get_u8());
-            }
-
-            Ok(lc)
-        }
-        else { Err("Wrong length") }
-    }
-
-
     pub fn get_length(&self) -> usize { 
         ListCard::size() + self.card.len()
     }
 }
 
 
-impl Serializable for ListCard {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.push(self.index_run);
-        vec_data.push(self.total_size);
-        vec_data.push(self.index);
-
-        for v in self.card.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
-
-        vec_data
-    }
-}
-
-
 // -- ListFunction -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Protocol)]
 pub struct ListFunction {
     pub index_run: u8,
     pub total_size: u8,
     pub index: u8,
+    #[protocol(tail)]
     pub card: Vec<u8>,
 }
 
@@ -834,51 +610,112 @@ impl ListFunction {
     }
 
 
-    pub const fn size() -> usize { 1 + 1 + 1 }
+    pub fn get_length(&self) -> usize { 
+        ListFunction::size() + self.card.len()
+    }
+}
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<ListFunction, &'static str> {
-        if slice_data.len() == ListFunction::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            let mut lc : ListFunction = ListFunction::new();
 
-            lc.index_run = ext.get_u8();
-            lc.total_size = ext.get_u8();
-            lc.index = ext.get_u8();
 
-            for _i in 0..(slice_data.len() - ListFunction::size())
-            {
-                lc.card.push(ext.get_u8());
-            }
 
-            Ok(lc)
+// -- ListAssembler -----------------------------------------------------------------------------------------------
+// 여러 번에 나누어 전송되는 ListCard, ListFunction을 하나의 카드 목록으로 합침
+// index_run 또는 total_size가 바뀌면 새로운 목록으로 보고, index(이번에 전송한 카드의 시작 위치)부터 카드를 채움
+// 카드 목록과 함수 목록은 따로 전송되므로 각각 ListAssembler를 사용
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListAssembler {
+    index_run: Option<u8>,
+    total_size: u8,
+    vec_card: Vec<Option<u8>>,
+}
+
+
+impl ListAssembler {
+    pub fn new() -> ListAssembler{
+        ListAssembler {
+            index_run: None,
+            total_size: 0,
+            vec_card: Vec::new(),
         }
-        else { Err("Wrong length") }
     }
 
 
-    pub fn get_length(&self) -> usize { 
-        ListFunction::size() + self.card.len()
+    pub fn clear(&mut self)
+    {
+        *self = ListAssembler::new();
     }
-}
 
 
-impl Serializable for ListFunction {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
+    // 받은 카드 수
+    pub fn get_count(&self) -> usize
+    {
+        self.vec_card.iter().filter(|card| card.is_some()).count()
+    }
 
-        vec_data.push(self.index_run);
-        vec_data.push(self.total_size);
-        vec_data.push(self.index);
 
-        for v in self.card.iter()
-        {
-            vec_data.extend_from_slice(&v.to_le_bytes());
+    // 목록이 완성되면 전체 카드를 반환하고 다음 목록을 받을 수 있게 초기화
+    pub fn push(&mut self, index_run: u8, total_size: u8, index: u8, slice_card: &[u8]) -> Option<Vec<u8>>
+    {
+        if self.index_run != Some(index_run) || self.total_size != total_size {
+            self.index_run = Some(index_run);
+            self.total_size = total_size;
+            self.vec_card = vec![None; total_size as usize];
         }
 
-        vec_data
+        // 범위를 벗어나는 카드는 무시
+        for (i, card) in slice_card.iter().enumerate() {
+            if let Some(slot) = self.vec_card.get_mut(index as usize + i) {
+                *slot = Some(*card);
+            }
+        }
+
+        if self.vec_card.iter().all(|card| card.is_some()) {
+            let vec_card = self.vec_card.iter().flatten().copied().collect();
+            self.clear();
+            Some(vec_card)
+        }
+        else { None }
+    }
+
+
+    pub fn push_list_card(&mut self, list_card: &ListCard) -> Option<Vec<u8>>
+    {
+        self.push(list_card.index_run, list_card.total_size, list_card.index, &list_card.card)
+    }
+
+
+    pub fn push_list_function(&mut self, list_function: &ListFunction) -> Option<Vec<u8>>
+    {
+        self.push(list_function.index_run, list_function.total_size, list_function.index, &list_function.card)
     }
 }
 
 
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_assembler() {
+        let mut assembler = ListAssembler::new();
+
+        // 순서가 바뀌어 도착한 페이지
+        let page_second = ListCard{index_run: 1, total_size: 5, index: 3, card: vec![0x51, 0x42]};
+        let page_first = ListCard{index_run: 1, total_size: 5, index: 0, card: vec![0x21, 0x41, 0x43]};
+
+        assert_eq!(assembler.push_list_card(&page_second), None);
+        assert_eq!(assembler.get_count(), 2);
+        assert_eq!(assembler.push_list_card(&page_first), Some(vec![0x21, 0x41, 0x43, 0x51, 0x42]));
+        assert_eq!(assembler.get_count(), 0);
+
+        // index_run이 바뀌면 이전에 받던 목록은 버림
+        assert_eq!(assembler.push_list_card(&ListCard{index_run: 2, total_size: 4, index: 0, card: vec![0x21, 0x41]}), None);
+        assert_eq!(assembler.push_list_card(&ListCard{index_run: 3, total_size: 4, index: 2, card: vec![0x55, 0x22]}), None);
+        assert_eq!(assembler.push_list_card(&ListCard{index_run: 3, total_size: 4, index: 0, card: vec![0x21, 0x45]}), Some(vec![0x21, 0x45, 0x55, 0x22]));
+
+        // 비어 있는 함수 목록
+        assert_eq!(assembler.push_list_function(&ListFunction{index_run: 4, total_size: 0, index: 0, card: Vec::new()}), Some(Vec::new()));
+    }
+}
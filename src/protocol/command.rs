@@ -3,7 +3,7 @@ use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
 use crate::protocol::{*};
-use crate::communication::extractor::Extractor;
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- CommandType -------------------------------------------------------------------------------------------
@@ -62,7 +62,7 @@ impl CommandType {
 
 
 // -- Command -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Command {
     pub command_type: CommandType,
     pub option: u8,
@@ -81,15 +81,15 @@ impl Command {
     pub const fn size() -> usize { 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Command, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Command, ProtocolError> {
         if slice_data.len() == Command::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Command{
-                command_type: CommandType::from_u8(ext.get_u8()),
-                option: ext.get_u8(),
+                command_type: enum_from_u8("Command::command_type", ext.get_u8()?)?,
+                option: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Command::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -107,7 +107,7 @@ impl Serializable for Command {
 
 
 // -- CommandLightEvent -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct CommandLightEvent {
     pub command: Command,
     pub light_event: light::Event,
@@ -123,25 +123,25 @@ impl CommandLightEvent {
     }
 
 
-    pub const fn size() -> usize { 5 }
+    pub const fn size() -> usize { Command::size() + light::Event::size() }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<CommandLightEvent, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<CommandLightEvent, ProtocolError> {
         if slice_data.len() == CommandLightEvent::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(CommandLightEvent{
                 command: Command{
-                    command_type: CommandType::from_u8(ext.get_u8()),
-                    option: ext.get_u8(),
+                    command_type: enum_from_u8("CommandLightEvent::command_type", ext.get_u8()?)?,
+                    option: ext.get_u8()?,
                 },
                 light_event: light::Event{
-                    event: ext.get_u8(),
-                    interval: ext.get_u16(),
-                    repeat: ext.get_u8(),
+                    event: ext.get_u8()?,
+                    interval: ext.get_u16()?,
+                    repeat: ext.get_u8()?,
                 }
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: CommandLightEvent::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -163,7 +163,7 @@ impl Serializable for CommandLightEvent {
 
 
 // -- CommandLightEventColor -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct CommandLightEventColor {
     pub command: Command,
     pub event: light::Event,
@@ -181,30 +181,30 @@ impl CommandLightEventColor {
     }
 
 
-    pub const fn size() -> usize { 7 }
+    pub const fn size() -> usize { Command::size() + light::Event::size() + light::Color::size() }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<CommandLightEventColor, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<CommandLightEventColor, ProtocolError> {
         if slice_data.len() == CommandLightEventColor::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok( CommandLightEventColor{
                 command: Command{
-                    command_type: CommandType::from_u8(ext.get_u8()),
-                    option: ext.get_u8(),
+                    command_type: enum_from_u8("CommandLightEventColor::command_type", ext.get_u8()?)?,
+                    option: ext.get_u8()?,
                 },
                 event: light::Event {
-                    event: ext.get_u8(),
-                    interval: ext.get_u16(),
-                    repeat: ext.get_u8(),
+                    event: ext.get_u8()?,
+                    interval: ext.get_u16()?,
+                    repeat: ext.get_u8()?,
                 },
                 color: light::Color {
-                    r: ext.get_u8(),
-                    g: ext.get_u8(),
-                    b: ext.get_u8(),
+                    r: ext.get_u8()?,
+                    g: ext.get_u8()?,
+                    b: ext.get_u8()?,
                 },
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: CommandLightEventColor::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -213,6 +213,8 @@ impl Serializable for CommandLightEventColor {
     fn to_vec(&self) -> Vec<u8> {
         let mut vec_data : Vec<u8> = Vec::new();
 
+        vec_data.push(self.command.command_type.into());
+        vec_data.extend_from_slice(&self.command.option.to_le_bytes());
         vec_data.extend_from_slice(&self.event.event.to_le_bytes());
         vec_data.extend_from_slice(&self.event.interval.to_le_bytes());
         vec_data.extend_from_slice(&self.event.repeat.to_le_bytes());
@@ -1,9 +1,9 @@
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- LostConnection -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct LostConnection {
     pub time_neutral: u16,
     pub time_landing: u16,
@@ -24,16 +24,16 @@ impl LostConnection {
     pub const fn size() -> usize { 8 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<LostConnection, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<LostConnection, ProtocolError> {
         if slice_data.len() == LostConnection::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(LostConnection{
-                time_neutral: ext.get_u16(),
-                time_landing: ext.get_u16(),
-                time_stop: ext.get_u32(),
+                time_neutral: ext.get_u16()?,
+                time_landing: ext.get_u16()?,
+                time_stop: ext.get_u32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: LostConnection::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -52,7 +52,7 @@ impl Serializable for LostConnection {
 
 
 // -- Rssi -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Rssi {
     pub rssi: i8,
 }
@@ -69,14 +69,14 @@ impl Rssi {
     pub const fn size() -> usize { 1 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Rssi, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Rssi, ProtocolError> {
         if slice_data.len() == Rssi::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Rssi{
-                rssi: ext.get_i8(),
+                rssi: ext.get_i8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Rssi::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -93,7 +93,7 @@ impl Serializable for Rssi {
 
 
 // -- Pairing -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Pairing {
     pub address: [u16; 3],
     pub scramble: u8,
@@ -114,16 +114,16 @@ impl Pairing {
     pub const fn size() -> usize { 11 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Pairing, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Pairing, ProtocolError> {
         if slice_data.len() == Pairing::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Pairing{
-                address: [ext.get_u16(), ext.get_u16(), ext.get_u16()],
-                scramble: ext.get_u8(),
-                channel: [ext.get_u8(), ext.get_u8(), ext.get_u8(), ext.get_u8()],
+                address: [ext.get_u16()?, ext.get_u16()?, ext.get_u16()?],
+                scramble: ext.get_u8()?,
+                channel: [ext.get_u8()?, ext.get_u8()?, ext.get_u8()?, ext.get_u8()?],
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Pairing::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -1,9 +1,9 @@
 use crate::protocol::{*};
-use crate::communication::extractor::Extractor;
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- WheelAccel8 -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct WheelAccel8 {
     pub wheel: i8,
     pub accel: i8,
@@ -22,15 +22,15 @@ impl WheelAccel8 {
     pub const fn size() -> usize { 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<WheelAccel8, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<WheelAccel8, ProtocolError> {
         if slice_data.len() == WheelAccel8::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(WheelAccel8{
-                wheel: ext.get_i8(),
-                accel: ext.get_i8(),
+                wheel: ext.get_i8()?,
+                accel: ext.get_i8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: WheelAccel8::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -48,7 +48,7 @@ impl Serializable for WheelAccel8 {
 
 
 // -- WheelAccel8AndRequestData -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct WheelAccel8AndRequestData {
     pub wheel: i8,
     pub accel: i8,
@@ -69,16 +69,16 @@ impl WheelAccel8AndRequestData {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<WheelAccel8AndRequestData, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<WheelAccel8AndRequestData, ProtocolError> {
         if slice_data.len() == WheelAccel8AndRequestData::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(WheelAccel8AndRequestData{
-                wheel: ext.get_i8(),
-                accel: ext.get_i8(),
-                data_type: DataType::from_u8(ext.get_u8()),
+                wheel: ext.get_i8()?,
+                accel: ext.get_i8()?,
+                data_type: enum_from_u8("WheelAccel8AndRequestData::data_type", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: WheelAccel8AndRequestData::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -97,7 +97,7 @@ impl Serializable for WheelAccel8AndRequestData {
 
 
 // -- Quad8 -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Quad8 {
     pub roll: i8,
     pub pitch: i8,
@@ -120,17 +120,17 @@ impl Quad8 {
     pub const fn size() -> usize { 4 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Quad8, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Quad8, ProtocolError> {
         if slice_data.len() == Quad8::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Quad8{
-                roll: ext.get_i8(),
-                pitch: ext.get_i8(),
-                yaw: ext.get_i8(),
-                throttle: ext.get_i8(),
+                roll: ext.get_i8()?,
+                pitch: ext.get_i8()?,
+                yaw: ext.get_i8()?,
+                throttle: ext.get_i8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Quad8::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -150,7 +150,7 @@ impl Serializable for Quad8 {
 
 
 // -- Quad8AndRequestData -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Quad8AndRequestData {
     pub roll: i8,
     pub pitch: i8,
@@ -175,18 +175,18 @@ impl Quad8AndRequestData {
     pub const fn size() -> usize { 5 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Quad8AndRequestData, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Quad8AndRequestData, ProtocolError> {
         if slice_data.len() == Quad8AndRequestData::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Quad8AndRequestData{
-                roll: ext.get_i8(),
-                pitch: ext.get_i8(),
-                yaw: ext.get_i8(),
-                throttle: ext.get_i8(),
-                data_type: DataType::from_u8(ext.get_u8()),
+                roll: ext.get_i8()?,
+                pitch: ext.get_i8()?,
+                yaw: ext.get_i8()?,
+                throttle: ext.get_i8()?,
+                data_type: enum_from_u8("Quad8AndRequestData::data_type", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Quad8AndRequestData::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -207,7 +207,7 @@ impl Serializable for Quad8AndRequestData {
 
 
 // -- Position16 -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Position16 {
     pub position_x: i16,
     pub position_y: i16,
@@ -234,19 +234,19 @@ impl Position16 {
     pub const fn size() -> usize { 12 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Position16, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Position16, ProtocolError> {
         if slice_data.len() == Position16::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Position16{
-                position_x: ext.get_i16(),
-                position_y: ext.get_i16(),
-                position_z: ext.get_i16(),
-                velocity: ext.get_i16(),
-                heading: ext.get_i16(),
-                rotational_velocity: ext.get_i16(),
+                position_x: ext.get_i16()?,
+                position_y: ext.get_i16()?,
+                position_z: ext.get_i16()?,
+                velocity: ext.get_i16()?,
+                heading: ext.get_i16()?,
+                rotational_velocity: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Position16::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -268,7 +268,7 @@ impl Serializable for Position16 {
 
 
 // -- Position -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -295,19 +295,19 @@ impl Position {
     pub const fn size() -> usize { 20 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Position, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Position, ProtocolError> {
         if slice_data.len() == Position::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Position{
-                x: ext.get_f32(),
-                y: ext.get_f32(),
-                z: ext.get_f32(),
-                velocity: ext.get_f32(),
-                heading: ext.get_i16(),
-                rotational_velocity: ext.get_i16(),
+                x: ext.get_f32()?,
+                y: ext.get_f32()?,
+                z: ext.get_f32()?,
+                velocity: ext.get_f32()?,
+                heading: ext.get_i16()?,
+                rotational_velocity: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Position::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -2,8 +2,8 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- Pixel ----------------------------------------------------------------------------------------------
@@ -91,7 +91,7 @@ impl Line {
 
 
 // -- ClearAll -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct ClearAll {
     pub pixel: Pixel,
 }
@@ -108,11 +108,11 @@ impl ClearAll {
     pub const fn size() -> usize { 1 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<ClearAll, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<ClearAll, ProtocolError> {
         if slice_data.len() == ClearAll::size() {
-            Ok(ClearAll{ pixel: Pixel::from_u8(slice_data[0]) })
+            Ok(ClearAll{ pixel: enum_from_u8("ClearAll::pixel", slice_data[0])? })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: ClearAll::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -129,7 +129,7 @@ impl Serializable for ClearAll {
 
 
 // -- Clear -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Clear {
     pub x: i16,
     pub y: i16,
@@ -154,18 +154,18 @@ impl Clear {
     pub const fn size() -> usize { 9 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Clear, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Clear, ProtocolError> {
         if slice_data.len() == Clear::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Clear{
-                x: ext.get_i16(),
-                y: ext.get_i16(),
-                width: ext.get_i16(),
-                height: ext.get_i16(),
-                pixel: Pixel::from_u8(ext.get_u8()),
+                x: ext.get_i16()?,
+                y: ext.get_i16()?,
+                width: ext.get_i16()?,
+                height: ext.get_i16()?,
+                pixel: enum_from_u8("Clear::pixel", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Clear::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -186,7 +186,7 @@ impl Serializable for Clear {
 
 
 // -- Invert -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Invert {
     pub x: i16,
     pub y: i16,
@@ -209,17 +209,17 @@ impl Invert {
     pub const fn size() -> usize { 8 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Invert, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Invert, ProtocolError> {
         if slice_data.len() == Invert::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Invert{
-                x: ext.get_i16(),
-                y: ext.get_i16(),
-                width: ext.get_i16(),
-                height: ext.get_i16(),
+                x: ext.get_i16()?,
+                y: ext.get_i16()?,
+                width: ext.get_i16()?,
+                height: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Invert::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -239,7 +239,7 @@ impl Serializable for Invert {
 
 
 // -- DrawPoint -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct DrawPoint {
     pub x: i16,
     pub y: i16,
@@ -260,16 +260,16 @@ impl DrawPoint {
     pub const fn size() -> usize { 5 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<DrawPoint, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<DrawPoint, ProtocolError> {
         if slice_data.len() == DrawPoint::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(DrawPoint{
-                x: ext.get_i16(),
-                y: ext.get_i16(),
-                pixel: Pixel::from_u8(ext.get_u8()),
+                x: ext.get_i16()?,
+                y: ext.get_i16()?,
+                pixel: enum_from_u8("DrawPoint::pixel", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: DrawPoint::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -288,7 +288,7 @@ impl Serializable for DrawPoint {
 
 
 // -- DrawLine -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct DrawLine {
     pub x1: i16,
     pub y1: i16,
@@ -315,19 +315,19 @@ impl DrawLine {
     pub const fn size() -> usize { 10 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<DrawLine, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<DrawLine, ProtocolError> {
         if slice_data.len() == DrawLine::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(DrawLine{
-                x1: ext.get_i16(),
-                y1: ext.get_i16(),
-                x2: ext.get_i16(),
-                y2: ext.get_i16(),
-                pixel: Pixel::from_u8(ext.get_u8()),
-                line: Line::from_u8(ext.get_u8()),
+                x1: ext.get_i16()?,
+                y1: ext.get_i16()?,
+                x2: ext.get_i16()?,
+                y2: ext.get_i16()?,
+                pixel: enum_from_u8("DrawLine::pixel", ext.get_u8()?)?,
+                line: enum_from_u8("DrawLine::line", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: DrawLine::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -349,7 +349,7 @@ impl Serializable for DrawLine {
 
 
 // -- DrawRect -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct DrawRect {
     pub x: i16,
     pub y: i16,
@@ -378,20 +378,20 @@ impl DrawRect {
     pub const fn size() -> usize { 11 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<DrawRect, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<DrawRect, ProtocolError> {
         if slice_data.len() == DrawRect::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(DrawRect{
-                x: ext.get_i16(),
-                y: ext.get_i16(),
-                width: ext.get_i16(),
-                height: ext.get_i16(),
-                pixel: Pixel::from_u8(ext.get_u8()),
-                fill: if ext.get_u8() == 0 { false } else { true },
-                line: Line::from_u8(ext.get_u8()),
+                x: ext.get_i16()?,
+                y: ext.get_i16()?,
+                width: ext.get_i16()?,
+                height: ext.get_i16()?,
+                pixel: enum_from_u8("DrawRect::pixel", ext.get_u8()?)?,
+                fill: if ext.get_u8()? == 0 { false } else { true },
+                line: enum_from_u8("DrawRect::line", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: DrawRect::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -414,7 +414,7 @@ impl Serializable for DrawRect {
 
 
 // -- DrawCircle -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct DrawCircle {
     pub x: i16,
     pub y: i16,
@@ -439,18 +439,18 @@ impl DrawCircle {
     pub const fn size() -> usize { 8 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<DrawCircle, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<DrawCircle, ProtocolError> {
         if slice_data.len() == DrawCircle::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(DrawCircle{
-                x: ext.get_i16(),
-                y: ext.get_i16(),
-                radius: ext.get_i16(),
-                pixel: Pixel::from_u8(ext.get_u8()),
-                fill: if ext.get_u8() == 0 { false } else { true },
+                x: ext.get_i16()?,
+                y: ext.get_i16()?,
+                radius: ext.get_i16()?,
+                pixel: enum_from_u8("DrawCircle::pixel", ext.get_u8()?)?,
+                fill: if ext.get_u8()? == 0 { false } else { true },
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: DrawCircle::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -471,7 +471,7 @@ impl Serializable for DrawCircle {
 
 
 // -- DrawString -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DrawString {
     pub x: i16,
     pub y: i16,
@@ -496,25 +496,18 @@ impl DrawString {
     pub const fn size() -> usize { 6 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<DrawString, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<DrawString, ProtocolError> {
         if slice_data.len() > DrawString::size() {
-            let mut vec_string: Vec<u8> = Vec::new();
-            vec_string.extend_from_slice(&slice_data[DrawString::size()..]);
-            let string: String = match String::from_utf8(vec_string) { 
-                Ok(s) => { s },
-                Err(_e) => { String::from("") },
-            };
-    
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(DrawString{
-                x: ext.get_i16(),
-                y: ext.get_i16(),
-                font: Font::from_u8(ext.get_u8()),
-                pixel: Pixel::from_u8(ext.get_u8()),
-                string: string,
+                x: ext.get_i16()?,
+                y: ext.get_i16()?,
+                font: enum_from_u8("DrawString::font", ext.get_u8()?)?,
+                pixel: enum_from_u8("DrawString::pixel", ext.get_u8()?)?,
+                string: String::from_utf8(ext.get_slice(ext.remaining())?.to_vec()).unwrap_or_default(),
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::Truncated{ expected: DrawString::size() + 1, actual: slice_data.len() }) }
     }
 
 
@@ -540,7 +533,7 @@ impl Serializable for DrawString {
 
 
 // -- DrawStringAlign -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DrawStringAlign {
     pub x_start: i16,
     pub x_end: i16,
@@ -569,27 +562,20 @@ impl DrawStringAlign {
     pub const fn size() -> usize { 9 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<DrawStringAlign, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<DrawStringAlign, ProtocolError> {
         if slice_data.len() > DrawStringAlign::size() {
-            let mut vec_string: Vec<u8> = Vec::new();
-            vec_string.extend_from_slice(&slice_data[DrawStringAlign::size()..]);
-            let string: String = match String::from_utf8(vec_string) { 
-                Ok(s) => { s },
-                Err(_e) => { String::from("") },
-            };
-    
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(DrawStringAlign{
-                x_start: ext.get_i16(),
-                x_end: ext.get_i16(),
-                y: ext.get_i16(),
-                align: Align::from_u8(ext.get_u8()),
-                font: Font::from_u8(ext.get_u8()),
-                pixel: Pixel::from_u8(ext.get_u8()),
-                string: string,
+                x_start: ext.get_i16()?,
+                x_end: ext.get_i16()?,
+                y: ext.get_i16()?,
+                align: enum_from_u8("DrawStringAlign::align", ext.get_u8()?)?,
+                font: enum_from_u8("DrawStringAlign::font", ext.get_u8()?)?,
+                pixel: enum_from_u8("DrawStringAlign::pixel", ext.get_u8()?)?,
+                string: String::from_utf8(ext.get_slice(ext.remaining())?.to_vec()).unwrap_or_default(),
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::Truncated{ expected: DrawStringAlign::size() + 1, actual: slice_data.len() }) }
     }
 
 
@@ -617,7 +603,7 @@ impl Serializable for DrawStringAlign {
 
 
 // -- DrawImage -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DrawImage {
     pub x: i16,
     pub y: i16,
@@ -642,18 +628,18 @@ impl DrawImage {
     pub const fn size() -> usize { 8 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<DrawImage, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<DrawImage, ProtocolError> {
         if slice_data.len() > DrawImage::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(DrawImage{
-                x: ext.get_i16(),
-                y: ext.get_i16(),
-                width: ext.get_i16(),
-                height: ext.get_i16(),
+                x: ext.get_i16()?,
+                y: ext.get_i16()?,
+                width: ext.get_i16()?,
+                height: ext.get_i16()?,
                 vec_image: slice_data[DrawImage::size()..].to_vec(),
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::Truncated{ expected: DrawImage::size() + 1, actual: slice_data.len() }) }
     }
 
 
@@ -2,8 +2,8 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- ModeCamera ----------------------------------------------------------------------------------------------
@@ -59,7 +59,7 @@ impl CommandType {
 
 
 // -- State -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct State {
     pub mode_camera: ModeCamera,
     pub fps: u16,
@@ -78,15 +78,15 @@ impl State {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<State, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<State, ProtocolError> {
         if slice_data.len() == State::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(State{
-                mode_camera: ModeCamera::from_u8(ext.get_u8()),
-                fps: ext.get_u16(),
+                mode_camera: enum_from_u8("State::mode_camera", ext.get_u8()?)?,
+                fps: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: State::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -105,7 +105,7 @@ impl Serializable for State {
 
 
 // -- Command -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Command {
     pub command_type: CommandType,
 }
@@ -122,14 +122,14 @@ impl Command {
     pub const fn size() -> usize { 1 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Command, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Command, ProtocolError> {
         if slice_data.len() == Command::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Command{
-                command_type: CommandType::from_u8(ext.get_u8()),
+                command_type: enum_from_u8("Command::command_type", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Command::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -3,12 +3,12 @@ use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 use std::vec;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- LidarData -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct LidarData {
     pub angle_radian_x1000: u16,
     pub distance_mm: u16,
@@ -29,26 +29,26 @@ impl LidarData {
 
 
     // LidarData 배열로 변환
-    pub fn parse(slice_data: &[u8]) -> Result<Vec<LidarData>, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Vec<LidarData>, ProtocolError> {
         if  slice_data.len() == LidarData::size() ||
             (slice_data.len() > 0 && slice_data.len() % LidarData::size() == 0)
         {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             let mut vec_lidar_data: Vec<LidarData> = Vec::new();
 
-            let length = slice_data.len() % LidarData::size();
+            let length = slice_data.len() / LidarData::size();
 
             for _i in 0..length
             {
                 vec_lidar_data.push(LidarData{
-                    angle_radian_x1000: ext.get_u16(),
-                    distance_mm: ext.get_u16(),
+                    angle_radian_x1000: ext.get_u16()?,
+                    distance_mm: ext.get_u16()?,
                 });
             }
 
             Ok(vec_lidar_data)
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: LidarData::size(), actual: slice_data.len() }) }
     }
 }
 
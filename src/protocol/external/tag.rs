@@ -3,12 +3,12 @@ use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 use std::vec;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- TagData -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct TagData {
     pub x: i16,
     pub y: i16,
@@ -34,29 +34,29 @@ impl TagData {
 
 
     // TagData 배열로 변환
-    pub fn parse(slice_data: &[u8]) -> Result<Vec<TagData>, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Vec<TagData>, ProtocolError> {
         if  slice_data.len() == TagData::size() ||
             (slice_data.len() > 0 && slice_data.len() % TagData::size() == 0)
         {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             let mut vec_tag_data: Vec<TagData> = Vec::new();
 
-            let length = slice_data.len() % TagData::size();
+            let length = slice_data.len() / TagData::size();
 
             for _i in 0..length
             {
                 vec_tag_data.push(TagData{
-                    x: ext.get_i16(),
-                    y: ext.get_i16(),
-                    width: ext.get_i16(),
-                    height: ext.get_i16(),
-                    id: ext.get_u16(),
+                    x: ext.get_i16()?,
+                    y: ext.get_i16()?,
+                    width: ext.get_i16()?,
+                    height: ext.get_i16()?,
+                    id: ext.get_u16()?,
                 });
             }
 
             Ok(vec_tag_data)
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: TagData::size(), actual: slice_data.len() }) }
     }
 }
 
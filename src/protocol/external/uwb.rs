@@ -1,9 +1,9 @@
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- Position -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -30,19 +30,19 @@ impl Position {
     pub const fn size() -> usize { 18 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Position, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Position, ProtocolError> {
         if slice_data.len() == Position::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Position{
-                x: ext.get_f32(),
-                y: ext.get_f32(),
-                z: ext.get_f32(),
-                system_time: ext.get_u32(),
-                anchor_group: ext.get_u8(),
-                error: ext.get_i8(),
+                x: ext.get_f32()?,
+                y: ext.get_f32()?,
+                z: ext.get_f32()?,
+                system_time: ext.get_u32()?,
+                anchor_group: ext.get_u8()?,
+                error: ext.get_i8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Position::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -2,8 +2,8 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- Direction ----------------------------------------------------------------------------------------------
@@ -87,7 +87,7 @@ impl Command {
 
 
 // -- JoystickBlock -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct JoystickBlock {
     pub x: i8,
     pub y: i8,
@@ -108,33 +108,24 @@ impl JoystickBlock {
 
 
     pub fn from_slice(slice_data: &[u8]) -> JoystickBlock {
-        if slice_data.len() == JoystickBlock::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            JoystickBlock{
-                x: ext.get_i8(),
-                y: ext.get_i8(),
-                direction: Direction::from_u8(ext.get_u8()),
-                event: Event::from_u8(ext.get_u8()),
-            }
-        }
-        else { JoystickBlock::new() }
+        JoystickBlock::parse(slice_data).unwrap_or_else(|_| JoystickBlock::new())
     }
 
 
     pub const fn size() -> usize { 4 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<JoystickBlock, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<JoystickBlock, ProtocolError> {
         if slice_data.len() == JoystickBlock::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(JoystickBlock{
-                x: ext.get_i8(),
-                y: ext.get_i8(),
-                direction: Direction::from_u8(ext.get_u8()),
-                event: Event::from_u8(ext.get_u8()),
+                x: ext.get_i8()?,
+                y: ext.get_i8()?,
+                direction: enum_from_u8("JoystickBlock::direction", ext.get_u8()?)?,
+                event: enum_from_u8("JoystickBlock::event", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: JoystickBlock::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -154,7 +145,7 @@ impl Serializable for JoystickBlock {
 
 
 // -- Joystick -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Joystick {
     pub left: JoystickBlock,
     pub right: JoystickBlock,
@@ -173,15 +164,15 @@ impl Joystick {
     pub const fn size() -> usize { JoystickBlock::size() * 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Joystick, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Joystick, ProtocolError> {
         if slice_data.len() == Joystick::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Joystick{
-                left: JoystickBlock::from_slice(&ext.get_array(JoystickBlock::size())),
-                right: JoystickBlock::from_slice(&ext.get_array(JoystickBlock::size())),
+                left: JoystickBlock::parse(ext.get_slice(JoystickBlock::size())?)?,
+                right: JoystickBlock::parse(ext.get_slice(JoystickBlock::size())?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Joystick::size(), actual: slice_data.len() }) }
     }
 }
 
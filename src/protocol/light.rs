@@ -2,8 +2,8 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- ModeLight ----------------------------------------------------------------------------------------------
@@ -36,7 +36,7 @@ impl ModeLight {
 
 
 // -- Color -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -57,16 +57,16 @@ impl Color {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Color, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Color, ProtocolError> {
         if slice_data.len() == Color::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Color{
-                r: ext.get_u8(),
-                g: ext.get_u8(),
-                b: ext.get_u8(),
+                r: ext.get_u8()?,
+                g: ext.get_u8()?,
+                b: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Color::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -86,7 +86,7 @@ impl Serializable for Color {
 
 
 // -- Manual -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Manual {
     pub flags: u16,
     pub brightness: u8,
@@ -105,15 +105,15 @@ impl Manual {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Manual, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Manual, ProtocolError> {
         if slice_data.len() == Manual::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Manual{
-                flags: ext.get_u16(),
-                brightness: ext.get_u8(),
+                flags: ext.get_u16()?,
+                brightness: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Manual::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -131,7 +131,7 @@ impl Serializable for Manual {
 
 
 // -- Mode -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Mode {
     pub mode: u8,
     pub interval: u16,
@@ -150,15 +150,15 @@ impl Mode {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Mode, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Mode, ProtocolError> {
         if slice_data.len() == Mode::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Mode{
-                mode: ext.get_u8(),
-                interval: ext.get_u16(),
+                mode: ext.get_u8()?,
+                interval: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Mode::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -176,7 +176,7 @@ impl Serializable for Mode {
 
 
 // -- Event -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Event {
     pub event: u8,
     pub interval: u16,
@@ -197,16 +197,16 @@ impl Event {
     pub const fn size() -> usize { 4 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Event, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Event, ProtocolError> {
         if slice_data.len() == Event::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Event{
-                event: ext.get_u8(),
-                interval: ext.get_u16(),
-                repeat: ext.get_u8(),
+                event: ext.get_u8()?,
+                interval: ext.get_u16()?,
+                repeat: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Event::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -225,7 +225,7 @@ impl Serializable for Event {
 
 
 // -- ModeColor -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct ModeColor {
     pub mode: Mode,
     pub color: Color,
@@ -244,22 +244,22 @@ impl ModeColor {
     pub const fn size() -> usize { 6 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<ModeColor, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<ModeColor, ProtocolError> {
         if slice_data.len() == ModeColor::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(ModeColor{
                 mode: Mode{
-                    mode: ext.get_u8(),
-                    interval: ext.get_u16(),
+                    mode: ext.get_u8()?,
+                    interval: ext.get_u16()?,
                 },
                 color: Color {
-                    r: ext.get_u8(),
-                    g: ext.get_u8(),
-                    b: ext.get_u8(),
+                    r: ext.get_u8()?,
+                    g: ext.get_u8()?,
+                    b: ext.get_u8()?,
                 },
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: ModeColor::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -280,7 +280,7 @@ impl Serializable for ModeColor {
 
 
 // -- EventColor -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct EventColor {
     pub event: Event,
     pub color: Color,
@@ -299,23 +299,23 @@ impl EventColor {
     pub const fn size() -> usize { 7 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<EventColor, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<EventColor, ProtocolError> {
         if slice_data.len() == EventColor::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok( EventColor{
                 event: Event {
-                    event: ext.get_u8(),
-                    interval: ext.get_u16(),
-                    repeat: ext.get_u8(),
+                    event: ext.get_u8()?,
+                    interval: ext.get_u16()?,
+                    repeat: ext.get_u8()?,
                 },
                 color: Color {
-                    r: ext.get_u8(),
-                    g: ext.get_u8(),
-                    b: ext.get_u8(),
+                    r: ext.get_u8()?,
+                    g: ext.get_u8()?,
+                    b: ext.get_u8()?,
                 },
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: EventColor::size(), actual: slice_data.len() }) }
     }
 }
 
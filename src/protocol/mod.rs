@@ -6,7 +6,7 @@
     1.  new() 함수는 입력값 없이 생성하여 빈 인스턴스 반환
     2.  parse(slice_data: &[u8]) 함수는 항상 슬라이스를 인풋으로 하되
         Extractor를 사용하여 데이터를 파싱하고,
-        Result<Data, &'static str>를 반환
+        Result<Data, ProtocolError>를 반환
     3.  size() 함수는 고정된 길이 값을 반환하되 가변길이인 경우에는 고정된 부분에 대한 값만 반환
     4.  get_length() 함수는 가변 길이 데이터를 전송하는 경우 고정된 데이터부분과 가변 데이터 부분을 모두 합한 데이터 길이 반환
     5.  to_vec(&self) 함수는 벡터 데이터를 반환
@@ -52,20 +52,32 @@
             호출하는 방식으로 만들었었으나 rust에서 함수를 만드는 스타일과 괴리가 있고, 굳이 두 개의 함수로 분리하지 않아도
             될 것으로 보여 이 방법을 사용하는 것으로 변경함
 
-        pub fn parse(slice_data: &[u8]) -> Result<Manual, &'static str> {
+        pub fn parse(slice_data: &[u8]) -> Result<Manual, ProtocolError> {
             if slice_data.len() == Manual::size() {
-                let mut ext: Extractor = Extractor::from_slice(slice_data);
+                let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
                 Ok(Manual{
-                    flags: ext.get_u16(),
-                    brightness: ext.get_u8(),
+                    flags: ext.get_u16()?,
+                    brightness: ext.get_u8()?,
                 })
             }
-            else { Err("Wrong length") }
+            else { Err(ProtocolError::LengthMismatch{ expected: Manual::size(), actual: slice_data.len() }) }
+        }
+
+
+    * derive
+        -   필드 순서대로 읽고 쓰는 단순한 구조체는 #[derive(Protocol)]로 size(), parse(), to_vec()를 생성함.
+            열거형, 배열, 가변 길이 필드에 사용하는 속성은 e_drone_derive 참고
+
+        #[derive(Debug, Copy, Clone, Protocol)]
+        pub struct Manual {
+            pub flags: u16,
+            pub brightness: u8,
         }
 
  */
 
 
+pub mod assembled;
 pub mod battle;
 pub mod button;
 pub mod buzzer;
@@ -83,12 +95,16 @@ pub mod navigation;
 pub mod sensor;
 pub mod vibrator;
 
+pub use e_drone_derive::Protocol;
+
+use std::fmt;
 use std::convert::TryFrom;
 use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 
 use crate::system::{*};
-use crate::communication::extractor::Extractor;
+use crate::communication::extractor::CheckedExtractor;
+use crate::communication::frame::Frame;
 
 
 // -- DataType ----------------------------------------------------------------------------------------------
@@ -208,14 +224,21 @@ pub enum DataType
     LidarData                           = 0xE2,     // Lidar 데이터
 
     // Linux Server Device
-    //ExternalSystemState                 = 0xE0,     // 0xE0
-    //ExternalSystemCommand               = 0xE1,     // 0xE1
-    //ExternalCameraState                 = 0xE2,     // 카메라 동작 상태
-    //ExternalCameraCommand               = 0xE3,     // 카메라 제어 명령
+    // 0xE0 ~ 0xE2는 Project P와 값이 겹치므로 DataType의 연관 상수로 정의하고 Profile에 따라 해석
+    ExternalCameraCommand               = 0xE3,     // 카메라 제어 명령
 }
 
 
 impl DataType {
+    // Linux Server Device (Profile::External)
+    #[allow(non_upper_case_globals)]
+    pub const ExternalSystemState: DataType = DataType::UwbPosition;      // 0xE0
+    #[allow(non_upper_case_globals)]
+    pub const ExternalSystemCommand: DataType = DataType::TagData;        // 0xE1
+    #[allow(non_upper_case_globals)]
+    pub const ExternalCameraState: DataType = DataType::LidarData;        // 0xE2
+
+
     // https://crates.io/crates/num_enum
     pub fn from_u8(data_u8: u8) -> DataType {
         match DataType::try_from( data_u8 ) {
@@ -226,24 +249,52 @@ impl DataType {
 }
 
 
+// -- Profile ----------------------------------------------------------------------------------------------
+// 장치 계열에 따라 같은 DataType 값(0xE0 ~ 0xE2)을 다르게 해석
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Profile {
+    #[default]
+    Uwb,        // Project P - UwbPosition, TagData, LidarData
+    External,   // Linux Server Device(Raspberry PI) - ExternalSystemState, ExternalSystemCommand, ExternalCameraState
+}
+
+
+impl Profile {
+    // Raspberry PI를 장착한 모델은 External, 그 외에는 Uwb
+    pub fn from_model_number(model_number: ModelNumber) -> Profile {
+        match model_number {
+            ModelNumber::Drone9DroneP3 => Profile::External,
+            _ => Profile::Uwb,
+        }
+    }
+}
+
+
 // -- Data -------------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Data {
     None,
     ErrorMessage (String),
 
     Header (Header),
 
+    // 정의되지 않은 DataType, DeviceType을 사용한 데이터 (Receiver::set_lenient)
+    // 받은 그대로 다시 보내려면 transfer::transfer_raw(&header_raw, &payload)
+    Unknown { header_raw: [u8; 4], payload: Vec<u8> },
+
     Ping (Ping),                            // 0x01
     Ack (Ack),                              // 0x02
     Error (Error),                          // 0x03
     Request (Request),                      // 0x04
     RequestOption (RequestOption),          // 0x04
+    Message (Message),                      // 0x05
     Address (Address),                      // 0x06
     Information (Information),              // 0x07
     Update (Update),                        // 0x08
     UpdateLocation (UpdateLocation),        // 0x09
+    SystemCount (SystemCount),              // 0x0B
     SystemInformation (SystemInformation),  // 0x0C
+    Registration (Registration),            // 0x0D
     Administrator (Administrator),          // 0x0E
 
     Monitor0 (monitor::Monitor0),           // 0x0F
@@ -263,6 +314,7 @@ pub enum Data {
 
     Pairing (communication::Pairing),   // 0x12
     Rssi (communication::Rssi),         // 0x13
+    Echo (Echo),                        // 0x17
 
     BattleIrMessage (battle::IrMessage),          // 0x1F
     BattleLightEventCommand (battle::LightEventCommand),          // 0x1F
@@ -279,6 +331,7 @@ pub enum Data {
     Attitude (sensor::Attitude),    // 0x41
     Position (sensor::Position),    // 0x42
     PositionVelocity (sensor::PositionVelocity),    // 0x42
+    Altitude (sensor::Altitude),    // 0x43
     Motion (sensor::Motion),        // 0x44
     Range (sensor::Range),          // 0x45
     Flow (sensor::Flow),            // 0x46
@@ -286,6 +339,7 @@ pub enum Data {
     Count (Count),                  // 0x50
     Bias (sensor::Bias),            // 0x51
     Trim (sensor::Trim),            // 0x52
+    Weight (sensor::Weight),        // 0x53
     LostConnection (communication::LostConnection),     // 0x54
     MagnetometerOffset (sensor::MagnetometerOffset),    // 0x55
 
@@ -315,13 +369,17 @@ pub enum Data {
     DisplayDrawStringAlign (display::DrawStringAlign),  // 0x87
     DisplayDrawImage (display::DrawImage),              // 0x88
 
-    CardClassify (card::Classify),          // 0x90
+    CardClassify (Vec<card::Classify>),     // 0x90
     CardRange (card::Range),                // 0x91
     CardRaw (card::Raw),                    // 0x92
     CardColor (card::Color),                // 0x93
     CardListCard (card::ListCard),          // 0x94
     CardListFunction (card::ListFunction),  // 0x95
 
+    InformationAssembledForController (assembled::ForController),  // 0xA0
+    InformationAssembledForEntry (assembled::ForEntry),            // 0xA1
+    InformationAssembledForByBlocks (assembled::ByBlocks),         // 0xA2
+
     NavigationTarget (navigation::Target),                    // 0xD0
     NavigationTargetLight (navigation::TargetLight),          // 0xD0
     NavigationLocation (navigation::Location),                // 0xD1
@@ -330,14 +388,260 @@ pub enum Data {
     NavigationCounter (navigation::Counter),                  // 0xD4
     NavigationSatellite (navigation::Satellite),              // 0xD5
     NavigationLocationAdjust (navigation::LocationAdjust),    // 0xD6
+    NavigationTargetEcef (navigation::TargetEcef),            // 0xD8
+    NavigationLocationEcef (navigation::LocationEcef),        // 0xD9
+    GpsRtkNavigationState (navigation::RtkNavigationState),   // 0xDA
+    GpsRtkExtendedRawMeasurementData (navigation::RtkExtendedRawMeasurementData), // 0xDB
     
     UwbPosition (external::uwb::Position),   // 0xE0
     TagData (Vec<external::tag::TagData>),   // 0xE1
     LidarData (Vec<external::lidar::LidarData>),   // 0xE2
-    //ExternalSystemState (external::system::State),      // 0xE0
-    //ExternalSystemCommand (external::system::Command),  // 0xE1
-    //ExternalCameraState (external::camera::State),      // 0xE2
-    //ExternalCameraCommand (external::camera::Command),  // 0xE3
+    ExternalSystemState (external::system::State),      // 0xE0
+    ExternalSystemCommand (external::system::Command),  // 0xE1
+    ExternalCameraState (external::camera::State),      // 0xE2
+    ExternalCameraCommand (external::camera::Command),  // 0xE3
+}
+
+
+impl Data {
+    // 데이터를 전송할 때 사용하는 DataType (전송할 수 없는 데이터는 DataType::None)
+    pub fn data_type(&self) -> DataType
+    {
+        match self {
+            Data::None | Data::ErrorMessage(_) | Data::Header(_) | Data::Unknown{..} => DataType::None,
+
+            Data::Ping(_) => DataType::Ping,
+            Data::Ack(_) => DataType::Ack,
+            Data::Error(_) => DataType::Error,
+            Data::Request(_) => DataType::Request,
+            Data::RequestOption(_) => DataType::Request,
+            Data::Message(_) => DataType::Message,
+            Data::Address(_) => DataType::Address,
+            Data::Information(_) => DataType::Information,
+            Data::Update(_) => DataType::Update,
+            Data::UpdateLocation(_) => DataType::UpdateLocation,
+            Data::SystemCount(_) => DataType::SystemCount,
+            Data::SystemInformation(_) => DataType::SystemInformation,
+            Data::Registration(_) => DataType::Registration,
+            Data::Administrator(_) => DataType::Administrator,
+            Data::Monitor0(_) => DataType::Monitor,
+            Data::Monitor4(_) => DataType::Monitor,
+            Data::Monitor8(_) => DataType::Monitor,
+            Data::WheelAccel8(_) => DataType::Control,
+            Data::WheelAccel8AndRequestData(_) => DataType::Control,
+            Data::Quad8(_) => DataType::Control,
+            Data::Quad8AndRequestData(_) => DataType::Control,
+            Data::ControlPosition16(_) => DataType::Control,
+            Data::ControlPosition(_) => DataType::Control,
+            Data::Command(_) => DataType::Command,
+            Data::CommandLightEvent(_) => DataType::Command,
+            Data::CommandLightEventColor(_) => DataType::Command,
+            Data::Pairing(_) => DataType::Pairing,
+            Data::Rssi(_) => DataType::Rssi,
+            Data::Echo(_) => DataType::Echo,
+            Data::BattleIrMessage(_) => DataType::Battle,
+            Data::BattleLightEventCommand(_) => DataType::Battle,
+            Data::BattleIrMessageLightEventCommand(_) => DataType::Battle,
+            Data::LightManual(_) => DataType::LightManual,
+            Data::LightMode(_) => DataType::LightMode,
+            Data::LightEvent(_) => DataType::LightEvent,
+            Data::RawMotion(_) => DataType::RawMotion,
+            Data::RawFlow(_) => DataType::RawFlow,
+            Data::State(_) => DataType::State,
+            Data::Attitude(_) => DataType::Attitude,
+            Data::Position(_) => DataType::Position,
+            Data::PositionVelocity(_) => DataType::Position,
+            Data::Altitude(_) => DataType::Altitude,
+            Data::Motion(_) => DataType::Motion,
+            Data::Range(_) => DataType::Range,
+            Data::Flow(_) => DataType::Flow,
+            Data::Count(_) => DataType::Count,
+            Data::Bias(_) => DataType::Bias,
+            Data::Trim(_) => DataType::Trim,
+            Data::Weight(_) => DataType::Weight,
+            Data::LostConnection(_) => DataType::LostConnection,
+            Data::MagnetometerOffset(_) => DataType::MagnetometerOffset,
+            Data::MotorV(_) => DataType::Motor,
+            Data::MotorRV(_) => DataType::Motor,
+            Data::MotorVA(_) => DataType::Motor,
+            Data::MotorRVA(_) => DataType::Motor,
+            Data::MotorSingleV(_) => DataType::MotorSingle,
+            Data::MotorSingleRV(_) => DataType::MotorSingle,
+            Data::Melody(_) => DataType::Buzzer,
+            Data::BuzzerScale(_) => DataType::Buzzer,
+            Data::BuzzerHz(_) => DataType::Buzzer,
+            Data::Button(_) => DataType::Button,
+            Data::Joystick(_) => DataType::Joystick,
+            Data::DisplayClearAll(_) => DataType::DisplayClear,
+            Data::DisplayClear(_) => DataType::DisplayClear,
+            Data::DisplayInvert(_) => DataType::DisplayInvert,
+            Data::DisplayDrawPoint(_) => DataType::DisplayDrawPoint,
+            Data::DisplayDrawLine(_) => DataType::DisplayDrawLine,
+            Data::DisplayDrawRect(_) => DataType::DisplayDrawRect,
+            Data::DisplayDrawCircle(_) => DataType::DisplayDrawCircle,
+            Data::DisplayDrawString(_) => DataType::DisplayDrawString,
+            Data::DisplayDrawStringAlign(_) => DataType::DisplayDrawStringAlign,
+            Data::DisplayDrawImage(_) => DataType::DisplayDrawImage,
+            Data::CardClassify(_) => DataType::CardClassify,
+            Data::CardRange(_) => DataType::CardRange,
+            Data::CardRaw(_) => DataType::CardRaw,
+            Data::CardColor(_) => DataType::CardColor,
+            Data::CardListCard(_) => DataType::CardList,
+            Data::CardListFunction(_) => DataType::CardFunctionList,
+            Data::InformationAssembledForController(_) => DataType::InformationAssembledForController,
+            Data::InformationAssembledForEntry(_) => DataType::InformationAssembledForEntry,
+            Data::InformationAssembledForByBlocks(_) => DataType::InformationAssembledForByBlocks,
+            Data::NavigationTarget(_) => DataType::NavigationTarget,
+            Data::NavigationTargetLight(_) => DataType::NavigationTarget,
+            Data::NavigationLocation(_) => DataType::NavigationLocation,
+            Data::NavigationMonitor(_) => DataType::NavigationMonitor,
+            Data::NavigationHeading(_) => DataType::NavigationHeading,
+            Data::NavigationCounter(_) => DataType::NavigationCounter,
+            Data::NavigationSatellite(_) => DataType::NavigationSatellite,
+            Data::NavigationLocationAdjust(_) => DataType::NavigationLocationAdjust,
+            Data::NavigationTargetEcef(_) => DataType::NavigationTargetEcef,
+            Data::NavigationLocationEcef(_) => DataType::NavigationLocationEcef,
+            Data::GpsRtkNavigationState(_) => DataType::GpsRtkNavigationState,
+            Data::GpsRtkExtendedRawMeasurementData(_) => DataType::GpsRtkExtendedRawMeasurementData,
+            Data::UwbPosition(_) => DataType::UwbPosition,
+            Data::TagData(_) => DataType::TagData,
+            Data::LidarData(_) => DataType::LidarData,
+            Data::ExternalSystemState(_) => DataType::ExternalSystemState,
+            Data::ExternalSystemCommand(_) => DataType::ExternalSystemCommand,
+            Data::ExternalCameraState(_) => DataType::ExternalCameraState,
+            Data::ExternalCameraCommand(_) => DataType::ExternalCameraCommand,
+        }
+    }
+
+
+    // 헤더를 제외한 데이터 부분. handler::check로 해석하면 같은 데이터가 됨
+    pub fn to_payload(&self) -> Option<Vec<u8>>
+    {
+        let payload: Vec<u8> = match self {
+            Data::None | Data::ErrorMessage(_) | Data::Header(_) | Data::Unknown{..} => return None,
+
+            Data::Ping(data) => data.to_vec(),
+            Data::Ack(data) => data.to_vec(),
+            Data::Error(data) => data.to_vec(),
+            Data::Request(data) => data.to_vec(),
+            Data::RequestOption(data) => data.to_vec(),
+            Data::Message(data) => data.to_vec(),
+            Data::Address(data) => data.to_vec(),
+            Data::Information(data) => data.to_vec(),
+            Data::Update(data) => data.to_vec(),
+            Data::UpdateLocation(data) => data.to_vec(),
+            Data::SystemCount(data) => data.to_vec(),
+            Data::SystemInformation(data) => data.to_vec(),
+            Data::Registration(data) => data.to_vec(),
+            Data::Administrator(data) => data.to_vec(),
+            Data::Monitor0(data) => [&[u8::from(monitor::HeaderType::Monitor0)][..], &data.to_vec()].concat(),
+            Data::Monitor4(data) => [&[u8::from(monitor::HeaderType::Monitor4)][..], &data.to_vec()].concat(),
+            Data::Monitor8(data) => [&[u8::from(monitor::HeaderType::Monitor8)][..], &data.to_vec()].concat(),
+            Data::WheelAccel8(data) => data.to_vec(),
+            Data::WheelAccel8AndRequestData(data) => data.to_vec(),
+            Data::Quad8(data) => data.to_vec(),
+            Data::Quad8AndRequestData(data) => data.to_vec(),
+            Data::ControlPosition16(data) => data.to_vec(),
+            Data::ControlPosition(data) => data.to_vec(),
+            Data::Command(data) => data.to_vec(),
+            Data::CommandLightEvent(data) => data.to_vec(),
+            Data::CommandLightEventColor(data) => data.to_vec(),
+            Data::Pairing(data) => data.to_vec(),
+            Data::Rssi(data) => data.to_vec(),
+            Data::Echo(data) => data.to_vec(),
+            Data::BattleIrMessage(data) => data.to_vec(),
+            Data::BattleLightEventCommand(data) => data.to_vec(),
+            Data::BattleIrMessageLightEventCommand(data) => data.to_vec(),
+            Data::LightManual(data) => data.to_vec(),
+            Data::LightMode(data) => data.to_vec(),
+            Data::LightEvent(data) => data.to_vec(),
+            Data::RawMotion(data) => data.to_vec(),
+            Data::RawFlow(data) => data.to_vec(),
+            Data::State(data) => data.to_vec(),
+            Data::Attitude(data) => data.to_vec(),
+            Data::Position(data) => data.to_vec(),
+            Data::PositionVelocity(data) => data.to_vec(),
+            Data::Altitude(data) => data.to_vec(),
+            Data::Motion(data) => data.to_vec(),
+            Data::Range(data) => data.to_vec(),
+            Data::Flow(data) => data.to_vec(),
+            Data::Count(data) => data.to_vec(),
+            Data::Bias(data) => data.to_vec(),
+            Data::Trim(data) => data.to_vec(),
+            Data::Weight(data) => data.to_vec(),
+            Data::LostConnection(data) => data.to_vec(),
+            Data::MagnetometerOffset(data) => data.to_vec(),
+            Data::MotorV(data) => data.to_vec(),
+            Data::MotorRV(data) => data.to_vec(),
+            Data::MotorVA(data) => data.to_vec(),
+            Data::MotorRVA(data) => data.to_vec(),
+            Data::MotorSingleV(data) => data.to_vec(),
+            Data::MotorSingleRV(data) => data.to_vec(),
+            Data::Melody(data) => data.to_vec(),
+            Data::BuzzerScale(data) => data.to_vec(),
+            Data::BuzzerHz(data) => data.to_vec(),
+            Data::Button(data) => data.to_vec(),
+            Data::Joystick(data) => data.to_vec(),
+            Data::DisplayClearAll(data) => data.to_vec(),
+            Data::DisplayClear(data) => data.to_vec(),
+            Data::DisplayInvert(data) => data.to_vec(),
+            Data::DisplayDrawPoint(data) => data.to_vec(),
+            Data::DisplayDrawLine(data) => data.to_vec(),
+            Data::DisplayDrawRect(data) => data.to_vec(),
+            Data::DisplayDrawCircle(data) => data.to_vec(),
+            Data::DisplayDrawString(data) => data.to_vec(),
+            Data::DisplayDrawStringAlign(data) => data.to_vec(),
+            Data::DisplayDrawImage(data) => data.to_vec(),
+            Data::CardClassify(data) => data.iter().flat_map(|d| d.to_vec()).collect(),
+            Data::CardRange(data) => data.to_vec(),
+            Data::CardRaw(data) => data.to_vec(),
+            Data::CardColor(data) => data.to_vec(),
+            Data::CardListCard(data) => data.to_vec(),
+            Data::CardListFunction(data) => data.to_vec(),
+            Data::InformationAssembledForController(data) => data.to_vec(),
+            Data::InformationAssembledForEntry(data) => data.to_vec(),
+            Data::InformationAssembledForByBlocks(data) => data.to_vec(),
+            Data::NavigationTarget(data) => data.to_vec(),
+            Data::NavigationTargetLight(data) => data.to_vec(),
+            Data::NavigationLocation(data) => data.to_vec(),
+            Data::NavigationMonitor(data) => data.to_vec(),
+            Data::NavigationHeading(data) => data.to_vec(),
+            Data::NavigationCounter(data) => data.to_vec(),
+            Data::NavigationSatellite(data) => data.to_vec(),
+            Data::NavigationLocationAdjust(data) => data.to_vec(),
+            Data::NavigationTargetEcef(data) => data.to_vec(),
+            Data::NavigationLocationEcef(data) => data.to_vec(),
+            Data::GpsRtkNavigationState(data) => data.to_vec(),
+            Data::GpsRtkExtendedRawMeasurementData(data) => data.to_vec(),
+            Data::UwbPosition(data) => data.to_vec(),
+            Data::TagData(data) => data.iter().flat_map(|d| d.to_vec()).collect(),
+            Data::LidarData(data) => data.iter().flat_map(|d| d.to_vec()).collect(),
+            Data::ExternalSystemState(data) => data.to_vec(),
+            Data::ExternalSystemCommand(data) => data.to_vec(),
+            Data::ExternalCameraState(data) => data.to_vec(),
+            Data::ExternalCameraCommand(data) => data.to_vec(),
+        };
+
+        Some(payload)
+    }
+
+
+    pub fn to_frame(&self, from: DeviceType, to: DeviceType) -> Option<Frame>
+    {
+        Frame::new(self.data_type(), from, to, self.to_payload()?)
+    }
+
+
+    // 데이터 모음(InformationAssembled)을 State, Attitude 등 개별 데이터로 나눔. 데이터 모음이 아니면 None
+    pub fn expand(&self) -> Option<Vec<Data>>
+    {
+        match self {
+            Data::InformationAssembledForController(data) => Some(data.to_vec_data()),
+            Data::InformationAssembledForEntry(data) => Some(data.to_vec_data()),
+            Data::InformationAssembledForByBlocks(data) => Some(data.to_vec_data()),
+            _ => None,
+        }
+    }
 }
 
 
@@ -349,8 +653,262 @@ pub trait Serializable
 }
 
 
+// -- ProtocolError -----------------------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolError {
+    LengthMismatch { expected: usize, actual: usize },          // 데이터 길이가 정의와 다름
+    UnknownValue { field: &'static str, value: u32 },          // 정의되지 않은 enum 값
+    CrcMismatch { expected: u16, actual: u16 },                 // CRC16 불일치 (expected: 계산한 값, actual: 수신한 값)
+    Truncated { expected: usize, actual: usize },               // 데이터가 중간에 끊김 (expected: 필요한 최소 길이)
+    Unsupported { data_type: DataType, length: usize },         // 해석할 수 없는 DataType 또는 길이
+}
+
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            ProtocolError::LengthMismatch{ expected, actual } => write!(f, "Wrong length (expected: {}, actual: {})", expected, actual),
+            ProtocolError::UnknownValue{ field, value } => write!(f, "Unknown value of {} ({:#X})", field, value),
+            ProtocolError::CrcMismatch{ expected, actual } => write!(f, "Wrong crc16 (expected: {:04X}, actual: {:04X})", expected, actual),
+            ProtocolError::Truncated{ expected, actual } => write!(f, "Truncated data (expected: {}, actual: {})", expected, actual),
+            ProtocolError::Unsupported{ data_type, length } => write!(f, "Unsupported data (DataType: {:?}, Length: {})", data_type, length),
+        }
+    }
+}
+
+
+impl std::error::Error for ProtocolError {}
+
+
+// num_enum(default)가 지정된 enum은 정의되지 않은 값도 기본값으로 변환하므로 변환 후 값을 다시 비교
+pub fn enum_from_u8<T>(field: &'static str, value: u8) -> Result<T, ProtocolError>
+where
+    T: TryFrom<u8> + Into<u8> + Copy,
+{
+    match T::try_from(value) {
+        Ok(data) if data.into() == value => Ok(data),
+        _ => Err(ProtocolError::UnknownValue{ field, value: value as u32 }),
+    }
+}
+
+
+// -- Payload -----------------------------------------------------------------------------------------------
+// DataType과 연결된 데이터 구조체
+// transfer::build, Drone::send로 DataType을 직접 지정하지 않고 전송할 수 있음
+pub trait Payload: Serializable + Sized
+{
+    const DATA_TYPE: DataType;
+    fn decode(slice_data: &[u8]) -> Result<Self, ProtocolError>;
+}
+
+
+macro_rules! impl_payload {
+    ($($t:ty => $data_type:ident;)*) => {
+        $(
+            impl Payload for $t
+            {
+                const DATA_TYPE: DataType = DataType::$data_type;
+
+                fn decode(slice_data: &[u8]) -> Result<Self, ProtocolError>
+                {
+                    <$t>::parse(slice_data)
+                }
+            }
+        )*
+    };
+}
+
+
+impl_payload! {
+    Ping => Ping;
+    Ack => Ack;
+    Error => Error;
+    Request => Request;
+    RequestOption => Request;
+    Message => Message;
+    Address => Address;
+    Information => Information;
+    Update => Update;
+    UpdateLocation => UpdateLocation;
+    SystemCount => SystemCount;
+    SystemInformation => SystemInformation;
+    Registration => Registration;
+    Administrator => Administrator;
+
+    control::WheelAccel8 => Control;
+    control::WheelAccel8AndRequestData => Control;
+    control::Quad8 => Control;
+    control::Quad8AndRequestData => Control;
+    control::Position16 => Control;
+    control::Position => Control;
+
+    command::Command => Command;
+    command::CommandLightEvent => Command;
+    command::CommandLightEventColor => Command;
+
+    communication::Pairing => Pairing;
+    communication::Rssi => Rssi;
+    Echo => Echo;
+
+    battle::IrMessage => Battle;
+    battle::LightEventCommand => Battle;
+    battle::IrMessageLightEventCommand => Battle;
+
+    light::Manual => LightManual;
+    light::Mode => LightMode;
+    // ModeColor는 LightMode와 LightDefault가 같이 사용하는 구조체이나 DATA_TYPE은 하나만 지정할 수 있으므로
+    // 바로 적용되는 LightMode로 연결. 기본 색상은 transfer::light_default처럼 LightDefault를 직접 지정하여 전송
+    light::ModeColor => LightMode;
+    light::Event => LightEvent;
+    light::EventColor => LightEvent;
+
+    sensor::RawMotion => RawMotion;
+    sensor::RawFlow => RawFlow;
+
+    State => State;
+    sensor::Attitude => Attitude;
+    sensor::Position => Position;
+    sensor::PositionVelocity => Position;
+    sensor::Altitude => Altitude;
+    sensor::Motion => Motion;
+    sensor::Range => Range;
+    sensor::Flow => Flow;
+
+    Count => Count;
+    sensor::Bias => Bias;
+    sensor::Trim => Trim;
+    sensor::Weight => Weight;
+    communication::LostConnection => LostConnection;
+    sensor::MagnetometerOffset => MagnetometerOffset;
+
+    motor::MotorV => Motor;
+    motor::MotorRV => Motor;
+    motor::MotorVA => Motor;
+    motor::MotorRVA => Motor;
+    motor::MotorSingleV => MotorSingle;
+    motor::MotorSingleRV => MotorSingle;
+
+    buzzer::Melody => Buzzer;
+    buzzer::BuzzerScale => Buzzer;
+    buzzer::BuzzerHz => Buzzer;
+    vibrator::Vibrator => Vibrator;
+
+    button::Button => Button;
+    joystick::Joystick => Joystick;
+
+    display::ClearAll => DisplayClear;
+    display::Clear => DisplayClear;
+    display::Invert => DisplayInvert;
+    display::DrawPoint => DisplayDrawPoint;
+    display::DrawLine => DisplayDrawLine;
+    display::DrawRect => DisplayDrawRect;
+    display::DrawCircle => DisplayDrawCircle;
+    display::DrawString => DisplayDrawString;
+    display::DrawStringAlign => DisplayDrawStringAlign;
+    display::DrawImage => DisplayDrawImage;
+
+    card::Classify => CardClassify;
+    card::Range => CardRange;
+    card::Raw => CardRaw;
+    card::Color => CardColor;
+    card::ListCard => CardList;
+    card::ListFunction => CardFunctionList;
+
+    assembled::ForController => InformationAssembledForController;
+    assembled::ForEntry => InformationAssembledForEntry;
+    assembled::ByBlocks => InformationAssembledForByBlocks;
+
+    navigation::Target => NavigationTarget;
+    navigation::TargetLight => NavigationTarget;
+    navigation::Location => NavigationLocation;
+    navigation::Monitor => NavigationMonitor;
+    navigation::Heading => NavigationHeading;
+    navigation::Counter => NavigationCounter;
+    navigation::Satellite => NavigationSatellite;
+    navigation::LocationAdjust => NavigationLocationAdjust;
+    navigation::TargetEcef => NavigationTargetEcef;
+    navigation::LocationEcef => NavigationLocationEcef;
+    navigation::RtkNavigationState => GpsRtkNavigationState;
+    navigation::RtkExtendedRawMeasurementData => GpsRtkExtendedRawMeasurementData;
+
+    external::uwb::Position => UwbPosition;
+    external::system::State => ExternalSystemState;
+    external::system::Command => ExternalSystemCommand;
+    external::camera::State => ExternalCameraState;
+    external::camera::Command => ExternalCameraCommand;
+}
+
+
+// -- Response -----------------------------------------------------------------------------------------------
+// Request로 요청하여 받을 수 있는 데이터
+pub trait Response: Payload
+{
+    fn from_data(data: &Data) -> Option<Self>;
+}
+
+
+macro_rules! impl_response {
+    ($($t:ty => $variant:ident;)*) => {
+        $(
+            impl Response for $t
+            {
+                fn from_data(data: &Data) -> Option<Self>
+                {
+                    match data {
+                        Data::$variant(data) => Some(data.clone()),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+
+impl_response! {
+    Address => Address;
+    Information => Information;
+    SystemCount => SystemCount;
+    SystemInformation => SystemInformation;
+    Registration => Registration;
+    Administrator => Administrator;
+    communication::Pairing => Pairing;
+    communication::Rssi => Rssi;
+    sensor::RawMotion => RawMotion;
+    sensor::RawFlow => RawFlow;
+    State => State;
+    sensor::Attitude => Attitude;
+    sensor::Position => Position;
+    sensor::PositionVelocity => PositionVelocity;
+    sensor::Altitude => Altitude;
+    sensor::Motion => Motion;
+    sensor::Range => Range;
+    sensor::Flow => Flow;
+    Count => Count;
+    sensor::Bias => Bias;
+    sensor::Trim => Trim;
+    sensor::Weight => Weight;
+    communication::LostConnection => LostConnection;
+    sensor::MagnetometerOffset => MagnetometerOffset;
+    button::Button => Button;
+    joystick::Joystick => Joystick;
+    navigation::Location => NavigationLocation;
+    navigation::Monitor => NavigationMonitor;
+    navigation::Heading => NavigationHeading;
+    navigation::Counter => NavigationCounter;
+    navigation::Satellite => NavigationSatellite;
+    navigation::LocationEcef => NavigationLocationEcef;
+    navigation::RtkNavigationState => GpsRtkNavigationState;
+    assembled::ForController => InformationAssembledForController;
+    assembled::ForEntry => InformationAssembledForEntry;
+    external::system::State => ExternalSystemState;
+    external::camera::State => ExternalCameraState;
+}
+
+
 // -- Header -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Header {
     pub data_type: DataType,
     pub length: u8,
@@ -371,7 +929,7 @@ impl Header {
 
     pub const fn size() -> usize { 4 }
 
-    pub fn parse(slice_data: &[u8]) -> Result<Header, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Header, ProtocolError> {
         if slice_data.len() == Header::size() {
             Ok(Header{
                 data_type: DataType::from_u8(slice_data[0]),
@@ -380,7 +938,7 @@ impl Header {
                 to: DeviceType::from_u8(slice_data[3]),
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Header::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -400,7 +958,7 @@ impl Serializable for Header {
 
 
 // -- Ping -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Ping {
     pub system_time: u64,
 }
@@ -417,14 +975,14 @@ impl Ping {
     pub const fn size() -> usize { 8 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Ping, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Ping, ProtocolError> {
         if slice_data.len() == Ping::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Ping{
-                system_time: ext.get_u64(),
+                system_time: ext.get_u64()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Ping::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -441,7 +999,7 @@ impl Serializable for Ping {
 
 
 // -- Ack -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Ack {
     pub system_time: u64,
     pub data_type: DataType,
@@ -462,16 +1020,16 @@ impl Ack {
     pub const fn size() -> usize { 11 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Ack, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Ack, ProtocolError> {
         if slice_data.len() == Ack::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Ack{
-                system_time: ext.get_u64(),
-                data_type: DataType::from_u8(ext.get_u8()),
-                crc16: ext.get_u16(),
+                system_time: ext.get_u64()?,
+                data_type: enum_from_u8("Ack::data_type", ext.get_u8()?)?,
+                crc16: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Ack::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -490,7 +1048,7 @@ impl Serializable for Ack {
 
 
 // -- Error -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Error {
     pub system_time: u64,
     pub error_flags_for_sensor: u32,
@@ -511,16 +1069,16 @@ impl Error {
     pub const fn size() -> usize { 16 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Error, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Error, ProtocolError> {
         if slice_data.len() == Error::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Error{
-                system_time: ext.get_u64(),
-                error_flags_for_sensor: ext.get_u32(),
-                error_flags_for_state: ext.get_u32(),
+                system_time: ext.get_u64()?,
+                error_flags_for_sensor: ext.get_u32()?,
+                error_flags_for_state: ext.get_u32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Error::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -539,7 +1097,7 @@ impl Serializable for Error {
 
 
 // -- Request -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Request {
     pub data_type: DataType,
 }
@@ -556,14 +1114,14 @@ impl Request {
     pub const fn size() -> usize { 1 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Request, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Request, ProtocolError> {
         if slice_data.len() == Request::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Request{
-                data_type: DataType::from_u8(ext.get_u8()),
+                data_type: enum_from_u8("Request::data_type", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Request::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -580,7 +1138,7 @@ impl Serializable for Request {
 
 
 // -- RequestOption -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct RequestOption {
     pub data_type: DataType,
     pub option: u32,
@@ -599,15 +1157,15 @@ impl RequestOption {
     pub const fn size() -> usize { 5 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<RequestOption, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<RequestOption, ProtocolError> {
         if slice_data.len() == RequestOption::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(RequestOption{
-                data_type: DataType::from_u8(ext.get_u8()),
-                option: ext.get_u32(),
+                data_type: enum_from_u8("RequestOption::data_type", ext.get_u8()?)?,
+                option: ext.get_u32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: RequestOption::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -624,8 +1182,26 @@ impl Serializable for RequestOption {
 }
 
 
+// -- Message -------------------------------------------------------------------------------------------
+// 펌웨어에서 출력하는 디버깅 문자열
+#[derive(Debug, Clone, PartialEq, Default, Protocol)]
+pub struct Message {
+    #[protocol(tail)]
+    pub message: String,
+}
+
+
+impl Message {
+    pub fn new() -> Message{
+        Message {
+            message: String::new(),
+        }
+    }
+}
+
+
 // -- SystemInformation -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct SystemInformation {
     pub crc32_bootloader: u32,
     pub crc32_application: u32,
@@ -644,15 +1220,15 @@ impl SystemInformation {
     pub const fn size() -> usize { 8 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<SystemInformation, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<SystemInformation, ProtocolError> {
         if slice_data.len() == SystemInformation::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(SystemInformation{
-                crc32_bootloader: ext.get_u32(),
-                crc32_application: ext.get_u32(),
+                crc32_bootloader: ext.get_u32()?,
+                crc32_application: ext.get_u32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: SystemInformation::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -669,8 +1245,46 @@ impl Serializable for SystemInformation {
 }
 
 
+// -- SystemCount -------------------------------------------------------------------------------------------
+#[derive(Debug, Copy, Clone, PartialEq, Default, Protocol)]
+pub struct SystemCount {
+    pub time_system: u32,       // 시스템 동작 시간(ms)
+    pub count_reset: u16,       // 시스템 재시작 횟수
+    pub count_error: u16,       // 오류 발생 횟수
+}
+
+
+impl SystemCount {
+    pub fn new() -> SystemCount{
+        SystemCount {
+            time_system: 0,
+            count_reset: 0,
+            count_error: 0,
+        }
+    }
+}
+
+
+// -- Registration -------------------------------------------------------------------------------------------
+// 암호화 데이터와 등록 데이터를 길이로 구분하므로 받은 데이터를 그대로 보관
+#[derive(Debug, Clone, PartialEq, Default, Protocol)]
+pub struct Registration {
+    #[protocol(tail)]
+    pub vec_data: Vec<u8>,
+}
+
+
+impl Registration {
+    pub fn new() -> Registration{
+        Registration {
+            vec_data: Vec::new(),
+        }
+    }
+}
+
+
 // -- Information -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Information {
     pub mode_update: ModeUpdate,
     pub model_number: ModelNumber,
@@ -697,19 +1311,19 @@ impl Information {
     pub const fn size() -> usize { 13 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Information, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Information, ProtocolError> {
         if slice_data.len() == Information::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Information{
-                mode_update: ModeUpdate::from_u8(ext.get_u8()),
-                model_number: ModelNumber::from_u32(ext.get_u32()),
-                version: Version::from_u32(ext.get_u32()),
-                year: ext.get_u16(),
-                month: ext.get_u8(),
-                day: ext.get_u8(),
+                mode_update: enum_from_u8("Information::mode_update", ext.get_u8()?)?,
+                model_number: ModelNumber::from_u32(ext.get_u32()?),
+                version: Version::from_u32(ext.get_u32()?),
+                year: ext.get_u16()?,
+                month: ext.get_u8()?,
+                day: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Information::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -731,7 +1345,7 @@ impl Serializable for Information {
 
 
 // -- UpdateLocation -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct UpdateLocation {
     pub index_block_next: u16,
 }
@@ -748,14 +1362,14 @@ impl UpdateLocation {
     pub const fn size() -> usize { 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<UpdateLocation, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<UpdateLocation, ProtocolError> {
         if slice_data.len() == UpdateLocation::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(UpdateLocation{
-                index_block_next: ext.get_u16(),
+                index_block_next: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: UpdateLocation::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -772,7 +1386,7 @@ impl Serializable for UpdateLocation {
 
 
 // -- Update -------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Update {
     pub index_block_next: u16,
     pub vec_data: Vec<u8>,
@@ -791,15 +1405,15 @@ impl Update {
     pub const fn size() -> usize { 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Update, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Update, ProtocolError> {
         if slice_data.len() > Update::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Update{
-                index_block_next: ext.get_u16(),
+                index_block_next: ext.get_u16()?,
                 vec_data: slice_data[Update::size()..].to_vec(),
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::Truncated{ expected: Update::size() + 1, actual: slice_data.len() }) }
     }
 
 
@@ -822,7 +1436,7 @@ impl Serializable for Update {
 
 
 // -- Address -------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Address {
     pub vec_address: Vec<u8>,
 }
@@ -839,13 +1453,13 @@ impl Address {
     pub const fn size() -> usize { 16 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Address, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Address, ProtocolError> {
         if slice_data.len() == Address::size() {
             Ok(Address{
                 vec_address: slice_data[..].to_vec(),
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Address::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -862,7 +1476,7 @@ impl Serializable for Address {
 
 
 // -- Administrator -------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Administrator {
     pub vec_key: Vec<u8>,
 }
@@ -879,13 +1493,13 @@ impl Administrator {
     pub const fn size() -> usize { 16 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Administrator, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Administrator, ProtocolError> {
         if slice_data.len() == Administrator::size() {
             Ok(Administrator{
                 vec_key: slice_data[..].to_vec(),
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Administrator::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -901,8 +1515,26 @@ impl Serializable for Administrator {
 }
 
 
+// -- Echo -------------------------------------------------------------------------------------------
+// 받은 데이터를 그대로 돌려받아 송수신 가능한 데이터 길이를 확인
+#[derive(Debug, Clone, PartialEq, Default, Protocol)]
+pub struct Echo {
+    #[protocol(tail)]
+    pub vec_data: Vec<u8>,
+}
+
+
+impl Echo {
+    pub fn new() -> Echo{
+        Echo {
+            vec_data: Vec::new(),
+        }
+    }
+}
+
+
 // -- Count -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Count {
     pub time_system: u32,
     pub time_flight: u32,
@@ -927,18 +1559,18 @@ impl Count {
     pub const fn size() -> usize { 14 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Count, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Count, ProtocolError> {
         if slice_data.len() == Count::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Count{
-                time_system: ext.get_u32(),
-                time_flight: ext.get_u32(),
-                count_takeoff: ext.get_u16(),
-                count_landing: ext.get_u16(),
-                count_accident: ext.get_u16(),
+                time_system: ext.get_u32()?,
+                time_flight: ext.get_u32()?,
+                count_takeoff: ext.get_u16()?,
+                count_landing: ext.get_u16()?,
+                count_accident: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Count::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -959,7 +1591,7 @@ impl Serializable for Count {
 
 
 // -- State -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct State {
     pub mode_system: ModeSystem,
     pub mode_flight: ModeFlight,
@@ -990,21 +1622,21 @@ impl State {
     pub const fn size() -> usize { 8 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<State, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<State, ProtocolError> {
         if slice_data.len() == State::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(State{
-                mode_system: ModeSystem::from_u8(ext.get_u8()),
-                mode_flight: ModeFlight::from_u8(ext.get_u8()),
-                mode_control_flight: ModeControlFlight::from_u8(ext.get_u8()),
-                mode_movement: ModeMovement::from_u8(ext.get_u8()),
-                headless: Headless::from_u8(ext.get_u8()),
-                control_speed: ext.get_u8(),
-                sensor_orientation: SensorOrientation::from_u8(ext.get_u8()),
-                battery: ext.get_u8(),
+                mode_system: enum_from_u8("State::mode_system", ext.get_u8()?)?,
+                mode_flight: enum_from_u8("State::mode_flight", ext.get_u8()?)?,
+                mode_control_flight: enum_from_u8("State::mode_control_flight", ext.get_u8()?)?,
+                mode_movement: enum_from_u8("State::mode_movement", ext.get_u8()?)?,
+                headless: enum_from_u8("State::headless", ext.get_u8()?)?,
+                control_speed: ext.get_u8()?,
+                sensor_orientation: enum_from_u8("State::sensor_orientation", ext.get_u8()?)?,
+                battery: ext.get_u8()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: State::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -1014,8 +1646,88 @@ impl Serializable for State {
         let mut vec_data : Vec<u8> = Vec::new();
 
         vec_data.push(self.mode_system.into());
+        vec_data.push(self.mode_flight.into());
+        vec_data.push(self.mode_control_flight.into());
+        vec_data.push(self.mode_movement.into());
+        vec_data.push(self.headless.into());
+        vec_data.push(self.control_speed);
+        vec_data.push(self.sensor_orientation.into());
+        vec_data.push(self.battery);
 
         vec_data
     }
 }
 
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::sensor::Attitude;
+
+    #[derive(Debug, Clone, Protocol)]
+    struct Sample {
+        flag: bool,
+        #[protocol(enum)]
+        target: DeviceType,
+        attitude: Attitude,
+        range: [i16; 2],
+        #[protocol(array = 3)]
+        color: Vec<u8>,
+        #[protocol(tail)]
+        string: String,
+    }
+
+    #[test]
+    fn derive_protocol() {
+        assert_eq!(Sample::size(), 1 + 1 + 6 + 4 + 3);
+        assert_eq!(sensor::Motion::size(), 18);
+
+        let sample = Sample{
+            flag: true,
+            target: DeviceType::Drone,
+            attitude: Attitude{ roll: 1, pitch: -2, yaw: 3 },
+            range: [-100, 200],
+            color: vec![10, 20, 30],
+            string: String::from("e_drone"),
+        };
+
+        let vec_data = sample.to_vec();
+        assert_eq!(vec_data.len(), Sample::size() + 7);
+        assert_eq!(&vec_data[..2], &[0x01, 0x10]);
+
+        let parsed = Sample::parse(&vec_data).unwrap();
+        assert!(parsed.flag);
+        assert_eq!(parsed.target, DeviceType::Drone);
+        assert_eq!((parsed.attitude.roll, parsed.attitude.pitch, parsed.attitude.yaw), (1, -2, 3));
+        assert_eq!(parsed.range, [-100, 200]);
+        assert_eq!(parsed.color, vec![10, 20, 30]);
+        assert_eq!(parsed.string, "e_drone");
+
+        // array = N은 Vec의 길이와 관계없이 N개로 씀 (부족하면 0으로 채우고 넘치면 자름)
+        let short = Sample{ color: vec![10], ..sample.clone() };
+        assert_eq!(short.to_vec().len(), vec_data.len());
+        assert_eq!(Sample::parse(&short.to_vec()).unwrap().color, vec![10, 0, 0]);
+
+        let long = Sample{ color: vec![10, 20, 30, 40], ..sample.clone() };
+        assert_eq!(long.to_vec(), vec_data);
+
+        let range = card::Range{ range: vec![-1, 2] };
+        assert_eq!(range.to_vec().len(), card::Range::size());
+        assert_eq!(card::Range::parse(&range.to_vec()).unwrap().range[..3], [-1, 2, 0]);
+
+        // 길이 부족, 알 수 없는 열거형 값
+        assert_eq!(Sample::parse(&vec_data[..5]).unwrap_err(), ProtocolError::Truncated{ expected: Sample::size(), actual: 5 });
+
+        let mut vec_wrong = vec_data.clone();
+        vec_wrong[1] = 0x55;
+        assert_eq!(Sample::parse(&vec_wrong).unwrap_err(), ProtocolError::UnknownValue{ field: "Sample::target", value: 0x55 });
+
+        // tail이 없으면 길이가 정확히 일치해야 함
+        assert_eq!(Attitude::parse(&[0; 7]).unwrap_err(), ProtocolError::LengthMismatch{ expected: 6, actual: 7 });
+
+        // 문자열이 중간에 잘려도 나머지 글자는 유지
+        assert_eq!(Message::parse(&[0x4F, 0x4B, 0xEB, 0x93]).unwrap().message, "OK\u{FFFD}");
+    }
+}
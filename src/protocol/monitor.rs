@@ -2,8 +2,8 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- HeaderType ----------------------------------------------------------------------------------------------
@@ -52,11 +52,75 @@ impl DataType {
             _ => { DataType::U8 },
         }
     }
+
+
+    // 값 하나의 크기(byte)
+    pub const fn size(&self) -> usize {
+        match self {
+            DataType::U8 | DataType::S8 => 1,
+            DataType::U16 | DataType::S16 => 2,
+            DataType::U32 | DataType::S32 | DataType::F32 => 4,
+            DataType::U64 | DataType::S64 | DataType::F64 => 8,
+        }
+    }
+}
+
+
+// 헤더 뒤에 남은 데이터를 data_type 크기의 값 배열로 읽음 (length_header: 앞에서 읽은 헤더 길이)
+fn parse_values(ext: &mut CheckedExtractor, data_type: DataType, length_header: usize) -> Result<Vec<f64>, ProtocolError>
+{
+    let remaining = ext.remaining();
+    if !remaining.is_multiple_of(data_type.size()) {
+        let expected = length_header + (remaining / data_type.size() + 1) * data_type.size();
+        return Err(ProtocolError::LengthMismatch{ expected, actual: length_header + remaining });
+    }
+
+    let mut vec_value = Vec::with_capacity(remaining / data_type.size());
+
+    while ext.remaining() > 0
+    {
+        let value : f64 = match data_type {
+            DataType::U8 => { ext.get_u8()? as f64 },
+            DataType::S8 => { ext.get_i8()? as f64 },
+            DataType::U16 => { ext.get_u16()? as f64 },
+            DataType::S16 => { ext.get_i16()? as f64 },
+            DataType::U32 => { ext.get_u32()? as f64 },
+            DataType::S32 => { ext.get_i32()? as f64 },
+            DataType::U64 => { ext.get_u64()? as f64 },
+            DataType::S64 => { ext.get_i64()? as f64 },
+            DataType::F32 => { ext.get_f32()? as f64 },
+            DataType::F64 => { ext.get_f64()? },
+        };
+
+        vec_value.push(value);
+    }
+
+    Ok(vec_value)
+}
+
+
+// 값 배열을 data_type 크기로 변환하여 추가
+fn extend_values(vec_data: &mut Vec<u8>, data_type: DataType, vec_value: &[f64])
+{
+    for v in vec_value.iter() {
+        match data_type {
+            DataType::U8 => vec_data.extend_from_slice(&(*v as u8).to_le_bytes()),
+            DataType::S8 => vec_data.extend_from_slice(&(*v as i8).to_le_bytes()),
+            DataType::U16 => vec_data.extend_from_slice(&(*v as u16).to_le_bytes()),
+            DataType::S16 => vec_data.extend_from_slice(&(*v as i16).to_le_bytes()),
+            DataType::U32 => vec_data.extend_from_slice(&(*v as u32).to_le_bytes()),
+            DataType::S32 => vec_data.extend_from_slice(&(*v as i32).to_le_bytes()),
+            DataType::U64 => vec_data.extend_from_slice(&(*v as u64).to_le_bytes()),
+            DataType::S64 => vec_data.extend_from_slice(&(*v as i64).to_le_bytes()),
+            DataType::F32 => vec_data.extend_from_slice(&(*v as f32).to_le_bytes()),
+            DataType::F64 => vec_data.extend_from_slice(&v.to_le_bytes()),
+        }
+    }
 }
 
 
 // -- MonitorType -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MonitorType {
     pub header_type: HeaderType,
 }
@@ -73,14 +137,14 @@ impl MonitorType {
     pub const fn size() -> usize { 1 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<MonitorType, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<MonitorType, ProtocolError> {
         if slice_data.len() == MonitorType::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(MonitorType{
-                header_type: HeaderType::from_u8(ext.get_u8()),
+                header_type: enum_from_u8("MonitorType::header_type", ext.get_u8()?)?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: MonitorType::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -97,7 +161,7 @@ impl Serializable for MonitorType {
 
 
 // -- Monitor0 -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Monitor0 {
     pub data_type: DataType,
     pub index: u8,
@@ -118,47 +182,23 @@ impl Monitor0 {
     pub const fn size() -> usize { 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Monitor0, &'static str> {
-        if slice_data.len() == Monitor0::size() {
+    pub fn parse(slice_data: &[u8]) -> Result<Monitor0, ProtocolError> {
+        if slice_data.len() >= Monitor0::size() {
             let mut monitor: Monitor0 = Monitor0::new();
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-
-            monitor.data_type = DataType::from_u8(ext.get_u8());
-            monitor.index = ext.get_u8();
-            monitor.vec_value = Vec::new();
-
-            loop
-            {
-                if ext.check_remain() > 0
-                {
-                    let value : f64 = match monitor.data_type {
-                        DataType::U8 => { ext.get_u8() as f64 },
-                        DataType::S8 => { ext.get_i8() as f64 },
-                        DataType::U16 => { ext.get_u16() as f64 },
-                        DataType::S16 => { ext.get_i16() as f64 },
-                        DataType::U32 => { ext.get_u32() as f64 },
-                        DataType::S32 => { ext.get_i32() as f64 },
-                        DataType::F32 => { ext.get_f32() as f64 },
-                        DataType::F64 => { ext.get_f64() },
-                        _ => { 0.0_f64 },
-                    };
-
-                    monitor.vec_value.push(value);
-                }
-                else
-                {
-                    break;
-                }
-            }
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
+
+            monitor.data_type = enum_from_u8("Monitor0::data_type", ext.get_u8()?)?;
+            monitor.index = ext.get_u8()?;
+            monitor.vec_value = parse_values(&mut ext, monitor.data_type, Monitor0::size())?;
 
             Ok(monitor)
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::Truncated{ expected: Monitor0::size(), actual: slice_data.len() }) }
     }
 
 
     pub fn get_length(&self) -> usize { 
-        Monitor0::size() + (self.vec_value.len() * 8)
+        Monitor0::size() + (self.vec_value.len() * self.data_type.size())
     }
 }
 
@@ -170,9 +210,7 @@ impl Serializable for Monitor0 {
         vec_data.push(self.data_type.into());
         vec_data.push(self.index);
 
-        for v in self.vec_value.iter() {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
+        extend_values(&mut vec_data, self.data_type, &self.vec_value);
 
         vec_data
     }
@@ -180,7 +218,7 @@ impl Serializable for Monitor0 {
 
 
 // -- Monitor4 -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Monitor4 {
     pub system_time: u32,
     pub data_type: DataType,
@@ -203,48 +241,24 @@ impl Monitor4 {
     pub const fn size() -> usize { 6 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Monitor4, &'static str> {
-        if slice_data.len() == Monitor4::size() {
+    pub fn parse(slice_data: &[u8]) -> Result<Monitor4, ProtocolError> {
+        if slice_data.len() >= Monitor4::size() {
             let mut monitor: Monitor4 = Monitor4::new();
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-
-            monitor.system_time = ext.get_u32();
-            monitor.data_type = DataType::from_u8(ext.get_u8());
-            monitor.index = ext.get_u8();
-            monitor.vec_value = Vec::new();
-
-            loop
-            {
-                if ext.check_remain() > 0
-                {
-                    let value : f64 = match monitor.data_type {
-                        DataType::U8 => { ext.get_u8() as f64 },
-                        DataType::S8 => { ext.get_i8() as f64 },
-                        DataType::U16 => { ext.get_u16() as f64 },
-                        DataType::S16 => { ext.get_i16() as f64 },
-                        DataType::U32 => { ext.get_u32() as f64 },
-                        DataType::S32 => { ext.get_i32() as f64 },
-                        DataType::F32 => { ext.get_f32() as f64 },
-                        DataType::F64 => { ext.get_f64() },
-                        _ => { 0.0_f64 },
-                    };
-
-                    monitor.vec_value.push(value);
-                }
-                else
-                {
-                    break;
-                }
-            }
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
+
+            monitor.system_time = ext.get_u32()?;
+            monitor.data_type = enum_from_u8("Monitor4::data_type", ext.get_u8()?)?;
+            monitor.index = ext.get_u8()?;
+            monitor.vec_value = parse_values(&mut ext, monitor.data_type, Monitor4::size())?;
 
             Ok(monitor)
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::Truncated{ expected: Monitor4::size(), actual: slice_data.len() }) }
     }
 
 
     pub fn get_length(&self) -> usize { 
-        Monitor4::size() + (self.vec_value.len() * 8)
+        Monitor4::size() + (self.vec_value.len() * self.data_type.size())
     }
 }
 
@@ -257,9 +271,7 @@ impl Serializable for Monitor4 {
         vec_data.push(self.data_type.into());
         vec_data.push(self.index);
 
-        for v in self.vec_value.iter() {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
+        extend_values(&mut vec_data, self.data_type, &self.vec_value);
 
         vec_data
     }
@@ -267,7 +279,7 @@ impl Serializable for Monitor4 {
 
 
 // -- Monitor8 -----------------------------------------------------------------------------------------------
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Monitor8 {
     pub system_time: u64,
     pub data_type: DataType,
@@ -290,48 +302,24 @@ impl Monitor8 {
     pub const fn size() -> usize { 10 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Monitor8, &'static str> {
-        if slice_data.len() == Monitor8::size() {
+    pub fn parse(slice_data: &[u8]) -> Result<Monitor8, ProtocolError> {
+        if slice_data.len() >= Monitor8::size() {
             let mut monitor: Monitor8 = Monitor8::new();
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-
-            monitor.system_time = ext.get_u64();
-            monitor.data_type = DataType::from_u8(ext.get_u8());
-            monitor.index = ext.get_u8();
-            monitor.vec_value = Vec::new();
-
-            loop
-            {
-                if ext.check_remain() > 0
-                {
-                    let value : f64 = match monitor.data_type {
-                        DataType::U8 => { ext.get_u8() as f64 },
-                        DataType::S8 => { ext.get_i8() as f64 },
-                        DataType::U16 => { ext.get_u16() as f64 },
-                        DataType::S16 => { ext.get_i16() as f64 },
-                        DataType::U32 => { ext.get_u32() as f64 },
-                        DataType::S32 => { ext.get_i32() as f64 },
-                        DataType::F32 => { ext.get_f32() as f64 },
-                        DataType::F64 => { ext.get_f64() },
-                        _ => { 0.0_f64 },
-                    };
-
-                    monitor.vec_value.push(value);
-                }
-                else
-                {
-                    break;
-                }
-            }
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
+
+            monitor.system_time = ext.get_u64()?;
+            monitor.data_type = enum_from_u8("Monitor8::data_type", ext.get_u8()?)?;
+            monitor.index = ext.get_u8()?;
+            monitor.vec_value = parse_values(&mut ext, monitor.data_type, Monitor8::size())?;
 
             Ok(monitor)
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::Truncated{ expected: Monitor8::size(), actual: slice_data.len() }) }
     }
 
 
     pub fn get_length(&self) -> usize { 
-        Monitor8::size() + (self.vec_value.len() * 8)
+        Monitor8::size() + (self.vec_value.len() * self.data_type.size())
     }
 }
 
@@ -344,9 +332,7 @@ impl Serializable for Monitor8 {
         vec_data.push(self.data_type.into());
         vec_data.push(self.index);
 
-        for v in self.vec_value.iter() {
-            vec_data.extend_from_slice(&v.to_le_bytes());
-        }
+        extend_values(&mut vec_data, self.data_type, &self.vec_value);
 
         vec_data
     }
@@ -1,9 +1,9 @@
 use crate::protocol::{*};
-use crate::communication::extractor::Extractor;
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- MotorV -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MotorV {
     pub value: i16,
 }
@@ -20,14 +20,14 @@ impl MotorV {
     pub const fn size() -> usize { 2 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<MotorV, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<MotorV, ProtocolError> {
         if slice_data.len() == MotorV::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(MotorV{
-                value: ext.get_i16(),
+                value: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: MotorV::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -44,7 +44,7 @@ impl Serializable for MotorV {
 
 
 // -- MotorRV -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MotorRV {
     pub rotation: Rotation,
     pub value: i16,
@@ -63,15 +63,15 @@ impl MotorRV {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<MotorRV, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<MotorRV, ProtocolError> {
         if slice_data.len() == MotorRV::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(MotorRV{
-                rotation: Rotation::from_u8(ext.get_u8()),
-                value: ext.get_i16(),
+                rotation: enum_from_u8("MotorRV::rotation", ext.get_u8()?)?,
+                value: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: MotorRV::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -89,7 +89,7 @@ impl Serializable for MotorRV {
 
 
 // -- MotorVA -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MotorVA {
     pub value: i16,
     pub adc: i16,
@@ -108,15 +108,15 @@ impl MotorVA {
     pub const fn size() -> usize { 4 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<MotorVA, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<MotorVA, ProtocolError> {
         if slice_data.len() == MotorVA::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(MotorVA{
-                value: ext.get_i16(),
-                adc: ext.get_i16(),
+                value: ext.get_i16()?,
+                adc: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: MotorVA::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -134,7 +134,7 @@ impl Serializable for MotorVA {
 
 
 // -- MotorRVA -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MotorRVA {
     pub rotation: Rotation,
     pub value: i16,
@@ -155,16 +155,16 @@ impl MotorRVA {
     pub const fn size() -> usize { 5 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<MotorRVA, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<MotorRVA, ProtocolError> {
         if slice_data.len() == MotorRVA::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(MotorRVA{
-                rotation: Rotation::from_u8(ext.get_u8()),
-                value: ext.get_i16(),
-                adc: ext.get_i16(),
+                rotation: enum_from_u8("MotorRVA::rotation", ext.get_u8()?)?,
+                value: ext.get_i16()?,
+                adc: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: MotorRVA::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -184,7 +184,7 @@ impl Serializable for MotorRVA {
 
 
 // -- MotorSingleV -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MotorSingleV {
     pub target: u8,
     pub value: i16,
@@ -203,15 +203,15 @@ impl MotorSingleV {
     pub const fn size() -> usize { 3 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<MotorSingleV, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<MotorSingleV, ProtocolError> {
         if slice_data.len() == MotorSingleV::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(MotorSingleV{
-                target: ext.get_u8(),
-                value: ext.get_i16(),
+                target: ext.get_u8()?,
+                value: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: MotorSingleV::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -229,7 +229,7 @@ impl Serializable for MotorSingleV {
 
 
 // -- MotorSingleRV -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MotorSingleRV {
     pub target: u8,
     pub rotation: Rotation,
@@ -250,16 +250,16 @@ impl MotorSingleRV {
     pub const fn size() -> usize { 4 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<MotorSingleRV, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<MotorSingleRV, ProtocolError> {
         if slice_data.len() == MotorSingleRV::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(MotorSingleRV{
-                target: ext.get_u8(),
-                rotation: Rotation::from_u8(ext.get_u8()),
-                value: ext.get_i16(),
+                target: ext.get_u8()?,
+                rotation: enum_from_u8("MotorSingleRV::rotation", ext.get_u8()?)?,
+                value: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: MotorSingleRV::size(), actual: slice_data.len() }) }
     }
 }
 
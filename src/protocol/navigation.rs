@@ -1,7 +1,7 @@
 use crate::protocol::{*};
-use crate::communication::extractor::Extractor;
+use crate::communication::extractor::CheckedExtractor;
 
-mod mode
+pub mod mode
 {
     use num_enum::IntoPrimitive;
     use num_enum::TryFromPrimitive;
@@ -101,7 +101,7 @@ mod mode
 
 
 // -- Target -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Target {
     pub index: u32,             //  4 명령 번호
 
@@ -140,23 +140,23 @@ impl Target {
     pub const fn size() -> usize { 48 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Target, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Target, ProtocolError> {
         if slice_data.len() == Target::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Target{
-                index:          ext.get_u32(),
-                mode_action:    mode::Action::from_u32(ext.get_u32()),
-                mode_option:    mode::Option::from_u32(ext.get_u32()),
-                time:           ext.get_u32(),
-                latitude:       ext.get_f64(),
-                longitude:      ext.get_f64(),
-                altitude:       ext.get_f32(),
-                speed:          ext.get_f32(),
-                heading:        ext.get_f32(),
-                rotational_speed: ext.get_f32(),
+                index:          ext.get_u32()?,
+                mode_action:    mode::Action::from_u32(ext.get_u32()?),
+                mode_option:    mode::Option::from_u32(ext.get_u32()?),
+                time:           ext.get_u32()?,
+                latitude:       ext.get_f64()?,
+                longitude:      ext.get_f64()?,
+                altitude:       ext.get_f32()?,
+                speed:          ext.get_f32()?,
+                heading:        ext.get_f32()?,
+                rotational_speed: ext.get_f32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Target::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -182,7 +182,7 @@ impl Serializable for Target {
 
 
 // -- TargetLight -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct TargetLight {
     pub index: u16,             //  2 명령 번호
 
@@ -221,23 +221,23 @@ impl TargetLight {
     pub const fn size() -> usize { 24 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<TargetLight, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<TargetLight, ProtocolError> {
         if slice_data.len() == TargetLight::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(TargetLight{
-                index:          ext.get_u16(),
-                mode_action:    mode::Action::from_u32(ext.get_u16() as u32),
-                mode_option:    mode::Option::from_u32(ext.get_u16() as u32),
-                time:           ext.get_u16(),
-                latitude:       ext.get_i32(),
-                longitude:      ext.get_i32(),
-                altitude:       ext.get_i16(),
-                speed:          ext.get_i16(),
-                heading:        ext.get_i16(),
-                rotational_speed: ext.get_i16(),
+                index:          ext.get_u16()?,
+                mode_action:    mode::Action::from_u32(ext.get_u16()? as u32),
+                mode_option:    mode::Option::from_u32(ext.get_u16()? as u32),
+                time:           ext.get_u16()?,
+                latitude:       ext.get_i32()?,
+                longitude:      ext.get_i32()?,
+                altitude:       ext.get_i16()?,
+                speed:          ext.get_i16()?,
+                heading:        ext.get_i16()?,
+                rotational_speed: ext.get_i16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: TargetLight::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -263,7 +263,7 @@ impl Serializable for TargetLight {
 
 
 // -- Location -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Location {
     pub fix_type: u8,
     pub num_sv: u8,
@@ -288,18 +288,18 @@ impl Location {
     pub const fn size() -> usize { 22 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Location, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Location, ProtocolError> {
         if slice_data.len() == Location::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Location{
-                fix_type:   ext.get_u8(),
-                num_sv:     ext.get_u8(),
-                latitude:   ext.get_f64(),
-                longitude:  ext.get_f64(),
-                altitude:   ext.get_f32(),
+                fix_type:   ext.get_u8()?,
+                num_sv:     ext.get_u8()?,
+                latitude:   ext.get_f64()?,
+                longitude:  ext.get_f64()?,
+                altitude:   ext.get_f32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Location::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -320,7 +320,7 @@ impl Serializable for Location {
 
 
 // -- LocationAdjust -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct LocationAdjust {
     pub mode_navigation: u8,
     pub latitude: f64,
@@ -341,16 +341,16 @@ impl LocationAdjust {
     pub const fn size() -> usize { 17 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<LocationAdjust, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<LocationAdjust, ProtocolError> {
         if slice_data.len() == LocationAdjust::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(LocationAdjust{
-                mode_navigation:    ext.get_u8(),
-                latitude:           ext.get_f64(),
-                longitude:          ext.get_f64(),
+                mode_navigation:    ext.get_u8()?,
+                latitude:           ext.get_f64()?,
+                longitude:          ext.get_f64()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: LocationAdjust::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -369,7 +369,7 @@ impl Serializable for LocationAdjust {
 
 
 // -- Monitor -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Monitor {
     pub mode_navigation: mode::Navigation,
     pub distance_to_target: f32,
@@ -391,21 +391,21 @@ impl Monitor {
     }
 
 
-    pub const fn size() -> usize { 17 }
+    pub const fn size() -> usize { 20 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Monitor, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Monitor, ProtocolError> {
         if slice_data.len() == Monitor::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Monitor{
-                mode_navigation:        mode::Navigation::from_u32(ext.get_u32()),
-                distance_to_target:     ext.get_f32(),
-                velocity:               ext.get_f32(),
-                heading:                ext.get_f32(),
-                time_remain:            ext.get_u32(),
+                mode_navigation:        mode::Navigation::from_u32(ext.get_u32()?),
+                distance_to_target:     ext.get_f32()?,
+                velocity:               ext.get_f32()?,
+                heading:                ext.get_f32()?,
+                time_remain:            ext.get_u32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Monitor::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -427,7 +427,7 @@ impl Serializable for Monitor {
 
 
 // -- Heading -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Heading {
     pub heading: f32,
     pub heading_path: f32,
@@ -447,20 +447,20 @@ impl Heading {
     }
 
 
-    pub const fn size() -> usize { 17 }
+    pub const fn size() -> usize { 16 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Heading, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Heading, ProtocolError> {
         if slice_data.len() == Heading::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Heading{
-                heading:            ext.get_f32(),
-                heading_path:       ext.get_f32(),
-                heading_to_target:  ext.get_f32(),
-                heading_error:      ext.get_f32(),
+                heading:            ext.get_f32()?,
+                heading_path:       ext.get_f32()?,
+                heading_to_target:  ext.get_f32()?,
+                heading_error:      ext.get_f32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Heading::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -480,7 +480,7 @@ impl Serializable for Heading {
 
 
 // -- Counter -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Counter {
     pub count_per_sec_receive: u16,
     pub count_per_sec_transfer: u16,
@@ -499,15 +499,15 @@ impl Counter {
     pub const fn size() -> usize { 4 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Counter, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Counter, ProtocolError> {
         if slice_data.len() == Counter::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Counter{
-                count_per_sec_receive:   ext.get_u16(),
-                count_per_sec_transfer:  ext.get_u16(),
+                count_per_sec_receive:   ext.get_u16()?,
+                count_per_sec_transfer:  ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Counter::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -526,7 +526,7 @@ impl Serializable for Counter {
 
 
 // -- Satellite -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Satellite {
     pub i_tow: u32,
     pub year: u16,
@@ -561,28 +561,28 @@ impl Satellite {
     }
 
 
-    pub const fn size() -> usize { 21 }
+    pub const fn size() -> usize { 20 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Satellite, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Satellite, ProtocolError> {
         if slice_data.len() == Satellite::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Satellite{
-                i_tow:      ext.get_u32(),
-                year:       ext.get_u16(),
-                month:      ext.get_u8(),
-                day:        ext.get_u8(),
-                hour:       ext.get_u8(),
-                min:        ext.get_u8(),
-                sec:        ext.get_u8(),
-                valid:      ext.get_u8(),
-                flags:      ext.get_u8(),
-                flags2:     ext.get_u8(),
-                g_speed:    ext.get_i32(),
-                p_dop:      ext.get_u16(),
+                i_tow:      ext.get_u32()?,
+                year:       ext.get_u16()?,
+                month:      ext.get_u8()?,
+                day:        ext.get_u8()?,
+                hour:       ext.get_u8()?,
+                min:        ext.get_u8()?,
+                sec:        ext.get_u8()?,
+                valid:      ext.get_u8()?,
+                flags:      ext.get_u8()?,
+                flags2:     ext.get_u8()?,
+                g_speed:    ext.get_i32()?,
+                p_dop:      ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Satellite::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -611,7 +611,7 @@ impl Serializable for Satellite {
 
 
 // -- LocationXYZ -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct LocationXYZ {
     pub x: f64,
     pub y: f64,
@@ -632,16 +632,16 @@ impl LocationXYZ {
     pub const fn size() -> usize { 20 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<LocationXYZ, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<LocationXYZ, ProtocolError> {
         if slice_data.len() == LocationXYZ::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(LocationXYZ{
-                x: ext.get_f64(),
-                y: ext.get_f64(),
-                z: ext.get_f32(),
+                x: ext.get_f64()?,
+                y: ext.get_f64()?,
+                z: ext.get_f32()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: LocationXYZ::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -659,3 +659,247 @@ impl Serializable for LocationXYZ {
 }
 
 
+// -- TargetEcef -----------------------------------------------------------------------------------------------
+// Target의 위도, 경도, 고도 대신 ECEF 좌표를 사용
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TargetEcef {
+    pub index: u32,                 //  4 명령 번호
+
+    pub mode_action: mode::Action,  //  8 행동
+    pub mode_option: mode::Option,  // 12 옵션
+
+    pub time: u32,                  // 16 실행 시간(ms)
+
+    pub location: LocationXYZ,      // 36 ECEF 좌표(m)
+    pub speed: f32,                 // 40 속도(m/s)
+
+    pub heading: f32,               // 44 헤딩(degree, compass 0.0 ~ 360.0)
+    pub rotational_speed: f32,      // 48 회전 속도(deg/sec) (-180.0(CW) ~ +180.0(CCW))
+}
+
+
+impl TargetEcef {
+    pub fn new() -> TargetEcef{
+        TargetEcef {
+            index:              0,
+            mode_action:        mode::Action::None,
+            mode_option:        mode::Option::None,
+            time:               0,
+            location:           LocationXYZ::new(),
+            speed:              0.0_f32,
+            heading:            0.0_f32,
+            rotational_speed:   0.0_f32,
+        }
+    }
+
+
+    pub const fn size() -> usize { 48 }
+
+
+    pub fn parse(slice_data: &[u8]) -> Result<TargetEcef, ProtocolError> {
+        if slice_data.len() == TargetEcef::size() {
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
+            Ok(TargetEcef{
+                index:          ext.get_u32()?,
+                mode_action:    mode::Action::from_u32(ext.get_u32()?),
+                mode_option:    mode::Option::from_u32(ext.get_u32()?),
+                time:           ext.get_u32()?,
+                location:       LocationXYZ::parse(ext.get_slice(LocationXYZ::size())?)?,
+                speed:          ext.get_f32()?,
+                heading:        ext.get_f32()?,
+                rotational_speed: ext.get_f32()?,
+            })
+        }
+        else { Err(ProtocolError::LengthMismatch{ expected: TargetEcef::size(), actual: slice_data.len() }) }
+    }
+}
+
+
+impl Default for TargetEcef {
+    fn default() -> TargetEcef {
+        TargetEcef::new()
+    }
+}
+
+
+impl Serializable for TargetEcef {
+    fn to_vec(&self) -> Vec<u8> {
+        let mut vec_data : Vec<u8> = Vec::new();
+
+        vec_data.extend_from_slice(&self.index.to_le_bytes());
+        vec_data.extend_from_slice(&self.mode_action.to_array());
+        vec_data.extend_from_slice(&self.mode_option.to_array());
+        vec_data.extend_from_slice(&self.time.to_le_bytes());
+        vec_data.extend_from_slice(&self.location.to_vec());
+        vec_data.extend_from_slice(&self.speed.to_le_bytes());
+        vec_data.extend_from_slice(&self.heading.to_le_bytes());
+        vec_data.extend_from_slice(&self.rotational_speed.to_le_bytes());
+
+        vec_data
+    }
+}
+
+
+// -- LocationEcef -----------------------------------------------------------------------------------------------
+// Location의 위도, 경도, 고도 대신 ECEF 좌표를 사용
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
+pub struct LocationEcef {
+    pub fix_type: u8,
+    pub num_sv: u8,
+    pub location: LocationXYZ,      // ECEF 좌표(m)
+}
+
+
+impl LocationEcef {
+    pub fn new() -> LocationEcef{
+        LocationEcef {
+            fix_type:   0,
+            num_sv:     0,
+            location:   LocationXYZ::new(),
+        }
+    }
+}
+
+
+impl Default for LocationEcef {
+    fn default() -> LocationEcef {
+        LocationEcef::new()
+    }
+}
+
+
+// -- CarrierSolution -----------------------------------------------------------------------------------------------
+// RTK 반송파 위상 해의 상태
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CarrierSolution {
+    None,       // RTK 해 없음
+    Float,      // 모호 정수 미결정
+    Fixed,      // 모호 정수 결정
+}
+
+
+// -- RtkNavigationState -----------------------------------------------------------------------------------------------
+// 기준국(Base)에 대한 이동국(Rover)의 상대 위치와 상태
+// u-blox UBX-NAV-RELPOSNED (version 0x01, 64 bytes)를 그대로 전달 (u-blox ZED-F9P Interface Description)
+#[derive(Debug, Copy, Clone, PartialEq, Default, Protocol)]
+pub struct RtkNavigationState {
+    pub version: u8,                // 메시지 버전(0x01)
+    pub reserved0: u8,
+    pub ref_station_id: u16,        // 기준국 ID
+
+    pub i_tow: u32,                 // GPS 주간 시각(ms)
+
+    pub rel_pos_n: i32,             // 북쪽 방향 상대 위치(cm)
+    pub rel_pos_e: i32,             // 동쪽 방향 상대 위치(cm)
+    pub rel_pos_d: i32,             // 아래 방향 상대 위치(cm)
+    pub rel_pos_length: i32,        // 기준국까지의 거리(cm)
+    pub rel_pos_heading: i32,       // 기준국에서 이동국 방향(1e-5 degree)
+    pub reserved1: [u8; 4],
+
+    pub rel_pos_hp_n: i8,           // 상대 위치의 cm 이하 값(0.1 mm, -99 ~ +99)
+    pub rel_pos_hp_e: i8,
+    pub rel_pos_hp_d: i8,
+    pub rel_pos_hp_length: i8,
+
+    pub acc_n: u32,                 // 정확도(0.1 mm)
+    pub acc_e: u32,
+    pub acc_d: u32,
+    pub acc_length: u32,
+    pub acc_heading: u32,           // 방향 정확도(1e-5 degree)
+    pub reserved2: [u8; 4],
+
+    pub flags: u32,                 // 상태 플래그
+}
+
+
+impl RtkNavigationState {
+    const FLAG_GNSS_FIX_OK: u32     = 0x0001;
+    const FLAG_DIFF_SOLUTION: u32   = 0x0002;
+    const FLAG_REL_POS_VALID: u32   = 0x0004;
+
+
+    pub fn new() -> RtkNavigationState{
+        RtkNavigationState::default()
+    }
+
+
+    // cm 단위 값과 0.1 mm 단위 값을 합한 상대 위치(m, 북/동/아래)
+    pub fn get_rel_pos_ned(&self) -> (f64, f64, f64) {
+        (
+            self.rel_pos_n as f64 * 0.01 + self.rel_pos_hp_n as f64 * 0.0001,
+            self.rel_pos_e as f64 * 0.01 + self.rel_pos_hp_e as f64 * 0.0001,
+            self.rel_pos_d as f64 * 0.01 + self.rel_pos_hp_d as f64 * 0.0001,
+        )
+    }
+
+
+    pub fn is_fix_ok(&self) -> bool { self.flags & RtkNavigationState::FLAG_GNSS_FIX_OK != 0 }
+
+    pub fn is_differential(&self) -> bool { self.flags & RtkNavigationState::FLAG_DIFF_SOLUTION != 0 }
+
+    pub fn is_relative_position_valid(&self) -> bool { self.flags & RtkNavigationState::FLAG_REL_POS_VALID != 0 }
+
+
+    pub fn get_carrier_solution(&self) -> CarrierSolution {
+        match (self.flags >> 3) & 0x03 {
+            1 => CarrierSolution::Float,
+            2 => CarrierSolution::Fixed,
+            _ => CarrierSolution::None,
+        }
+    }
+}
+
+
+// -- RtkRawMeasurement -----------------------------------------------------------------------------------------------
+// 위성 신호 하나의 측정값
+// u-blox UBX-RXM-RAWX (version 0x01)의 반복 블럭(32 bytes)과 같은 배치 (u-blox ZED-F9P Interface Description)
+#[derive(Debug, Copy, Clone, PartialEq, Default, Protocol)]
+pub struct RtkRawMeasurement {
+    pub pr_mes: f64,                // 의사 거리(m)
+    pub cp_mes: f64,                // 반송파 위상(cycles)
+    pub do_mes: f32,                // 도플러(Hz)
+    pub gnss_id: u8,
+    pub sv_id: u8,
+    pub sig_id: u8,
+    pub freq_id: u8,
+    pub locktime: u16,              // 반송파 위상 추적 시간(ms)
+    pub cno: u8,                    // 신호 세기(dBHz)
+    pub pr_stdev: u8,
+    pub cp_stdev: u8,
+    pub do_stdev: u8,
+    pub trk_stat: u8,
+    pub reserved: u8,
+}
+
+
+impl RtkRawMeasurement {
+    pub fn new() -> RtkRawMeasurement{
+        RtkRawMeasurement::default()
+    }
+}
+
+
+// -- RtkExtendedRawMeasurementData -----------------------------------------------------------------------------------------------
+// 수신기 시각과 위성별 측정값
+// u-blox UBX-RXM-RAWX (version 0x01)의 머리(16 bytes)와 반복 블럭을 그대로 전달
+#[derive(Debug, Clone, PartialEq, Default, Protocol)]
+pub struct RtkExtendedRawMeasurementData {
+    pub rcv_tow: f64,               // 수신기 주간 시각(s)
+    pub week: u16,                  // GPS 주
+    pub leap_s: i8,                 // GPS-UTC 윤초(s)
+    pub num_meas: u8,               // 측정값 수
+    pub rec_stat: u8,               // 수신기 상태
+    pub version: u8,
+    pub reserved: [u8; 2],
+    #[protocol(tail)]
+    pub vec_measurement: Vec<RtkRawMeasurement>,
+}
+
+
+impl RtkExtendedRawMeasurementData {
+    pub fn new() -> RtkExtendedRawMeasurementData{
+        RtkExtendedRawMeasurementData::default()
+    }
+}
+
+
@@ -1,9 +1,8 @@
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::Protocol;
 
 
 // -- RawMotion -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct RawMotion {
     pub accel_x: i16,
     pub accel_y: i16,
@@ -25,48 +24,11 @@ impl RawMotion {
             gyro_yaw: 0,
         }
     }
-
-
-    pub const fn size() -> usize { 12 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<RawMotion, &'static str> {
-        if slice_data.len() == RawMotion::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(RawMotion{
-                accel_x: ext.get_i16(),
-                accel_y: ext.get_i16(),
-                accel_z: ext.get_i16(),
-        
-                gyro_roll: ext.get_i16(),
-                gyro_pitch: ext.get_i16(),
-                gyro_yaw: ext.get_i16(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for RawMotion {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.accel_x.to_le_bytes());
-        vec_data.extend_from_slice(&self.accel_y.to_le_bytes());
-        vec_data.extend_from_slice(&self.accel_z.to_le_bytes());
-
-        vec_data.extend_from_slice(&self.gyro_roll.to_le_bytes());
-        vec_data.extend_from_slice(&self.gyro_pitch.to_le_bytes());
-        vec_data.extend_from_slice(&self.gyro_yaw.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 // -- RawFlow -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct RawFlow {
     pub x: f32,
     pub y: f32,
@@ -80,38 +42,11 @@ impl RawFlow {
             y: 0.0_f32,
         }
     }
-
-
-    pub const fn size() -> usize { 8 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<RawFlow, &'static str> {
-        if slice_data.len() == RawFlow::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(RawFlow{
-                x: ext.get_f32(),
-                y: ext.get_f32(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for RawFlow {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.x.to_le_bytes());
-        vec_data.extend_from_slice(&self.y.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 // -- Attitude -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct Attitude {
     pub roll: i16,
     pub pitch: i16,
@@ -127,41 +62,12 @@ impl Attitude {
             yaw: 0,
         }
     }
-
-
-    pub const fn size() -> usize { 6 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Attitude, &'static str> {
-        if slice_data.len() == Attitude::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(Attitude{
-                roll: ext.get_i16(),
-                pitch: ext.get_i16(),
-                yaw: ext.get_i16(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Attitude {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.roll.to_le_bytes());
-        vec_data.extend_from_slice(&self.pitch.to_le_bytes());
-        vec_data.extend_from_slice(&self.yaw.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 
 // -- Motion -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct Motion {
     pub accel_x: i16,
     pub accel_y: i16,
@@ -189,57 +95,12 @@ impl Motion {
             angle_yaw: 0,
         }
     }
-
-
-    pub const fn size() -> usize { 18 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Motion, &'static str> {
-        if slice_data.len() == Motion::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(Motion{
-                accel_x: ext.get_i16(),
-                accel_y: ext.get_i16(),
-                accel_z: ext.get_i16(),
-        
-                gyro_roll: ext.get_i16(),
-                gyro_pitch: ext.get_i16(),
-                gyro_yaw: ext.get_i16(),
-        
-                angle_roll: ext.get_i16(),
-                angle_pitch: ext.get_i16(),
-                angle_yaw: ext.get_i16(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Motion {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.accel_x.to_le_bytes());
-        vec_data.extend_from_slice(&self.accel_y.to_le_bytes());
-        vec_data.extend_from_slice(&self.accel_z.to_le_bytes());
-
-        vec_data.extend_from_slice(&self.gyro_roll.to_le_bytes());
-        vec_data.extend_from_slice(&self.gyro_pitch.to_le_bytes());
-        vec_data.extend_from_slice(&self.gyro_yaw.to_le_bytes());
-
-        vec_data.extend_from_slice(&self.angle_roll.to_le_bytes());
-        vec_data.extend_from_slice(&self.angle_pitch.to_le_bytes());
-        vec_data.extend_from_slice(&self.angle_yaw.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 
 // -- Range -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct Range {
     pub left: i16,
     pub front: i16,
@@ -261,48 +122,11 @@ impl Range {
             bottom: 0,
         }
     }
-
-
-    pub const fn size() -> usize { 12 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Range, &'static str> {
-        if slice_data.len() == Range::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(Range{
-                left: ext.get_i16(),
-                front: ext.get_i16(),
-                right: ext.get_i16(),
-        
-                rear: ext.get_i16(),
-                top: ext.get_i16(),
-                bottom: ext.get_i16(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Range {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.left.to_le_bytes());
-        vec_data.extend_from_slice(&self.front.to_le_bytes());
-        vec_data.extend_from_slice(&self.right.to_le_bytes());
-
-        vec_data.extend_from_slice(&self.rear.to_le_bytes());
-        vec_data.extend_from_slice(&self.top.to_le_bytes());
-        vec_data.extend_from_slice(&self.bottom.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 // -- Position -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -318,40 +142,11 @@ impl Position {
             z: 0.0_f32,
         }
     }
-
-
-    pub const fn size() -> usize { 12 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Position, &'static str> {
-        if slice_data.len() == Position::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(Position{
-                x: ext.get_f32(),
-                y: ext.get_f32(),
-                z: ext.get_f32(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Position {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.x.to_le_bytes());
-        vec_data.extend_from_slice(&self.y.to_le_bytes());
-        vec_data.extend_from_slice(&self.z.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 // -- PositionVelocity -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct PositionVelocity {
     pub x: f32,
     pub y: f32,
@@ -373,46 +168,33 @@ impl PositionVelocity {
             vz: 0.0_f32,
         }
     }
-
-
-    pub const fn size() -> usize { 24 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<PositionVelocity, &'static str> {
-        if slice_data.len() == PositionVelocity::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(PositionVelocity{
-                x: ext.get_f32(),
-                y: ext.get_f32(),
-                z: ext.get_f32(),
-                vx: ext.get_f32(),
-                vy: ext.get_f32(),
-                vz: ext.get_f32(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
 }
 
 
-impl Serializable for PositionVelocity {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
+// -- Altitude -----------------------------------------------------------------------------------------------
+#[derive(Debug, Copy, Clone, PartialEq, Default, Protocol)]
+pub struct Altitude {
+    pub temperature: f32,   // 온도(℃)
+    pub pressure: f32,      // 기압(Pa)
+    pub altitude: f32,      // 기압으로 계산한 고도(m)
+    pub range_height: f32,  // 바닥 거리 센서로 측정한 높이(m)
+}
 
-        vec_data.extend_from_slice(&self.x.to_le_bytes());
-        vec_data.extend_from_slice(&self.y.to_le_bytes());
-        vec_data.extend_from_slice(&self.z.to_le_bytes());
-        vec_data.extend_from_slice(&self.vx.to_le_bytes());
-        vec_data.extend_from_slice(&self.vy.to_le_bytes());
-        vec_data.extend_from_slice(&self.vz.to_le_bytes());
 
-        vec_data
+impl Altitude {
+    pub fn new() -> Altitude{
+        Altitude {
+            temperature: 0.0_f32,
+            pressure: 0.0_f32,
+            altitude: 0.0_f32,
+            range_height: 0.0_f32,
+        }
     }
 }
 
 
 // -- Flow -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct Flow {
     pub x: f32,
     pub y: f32,
@@ -428,41 +210,12 @@ impl Flow {
             z: 0.0_f32,
         }
     }
-
-
-    pub const fn size() -> usize { 12 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Flow, &'static str> {
-        if slice_data.len() == Flow::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(Flow{
-                x: ext.get_f32(),
-                y: ext.get_f32(),
-                z: ext.get_f32(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Flow {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.x.to_le_bytes());
-        vec_data.extend_from_slice(&self.y.to_le_bytes());
-        vec_data.extend_from_slice(&self.z.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 
 // -- Bias -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct Bias {
     pub accel_x: i16,
     pub accel_y: i16,
@@ -484,49 +237,12 @@ impl Bias {
             gyro_yaw: 0,
         }
     }
-
-
-    pub const fn size() -> usize { 12 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<Bias, &'static str> {
-        if slice_data.len() == Bias::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(Bias{
-                accel_x: ext.get_i16(),
-                accel_y: ext.get_i16(),
-                accel_z: ext.get_i16(),
-        
-                gyro_roll: ext.get_i16(),
-                gyro_pitch: ext.get_i16(),
-                gyro_yaw: ext.get_i16(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for Bias {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.accel_x.to_le_bytes());
-        vec_data.extend_from_slice(&self.accel_y.to_le_bytes());
-        vec_data.extend_from_slice(&self.accel_z.to_le_bytes());
-
-        vec_data.extend_from_slice(&self.gyro_roll.to_le_bytes());
-        vec_data.extend_from_slice(&self.gyro_pitch.to_le_bytes());
-        vec_data.extend_from_slice(&self.gyro_yaw.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
 
 // -- Trim -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct Trim {
     pub roll: i16,
     pub pitch: i16,
@@ -544,43 +260,29 @@ impl Trim {
             throttle: 0,
         }
     }
+}
 
 
-    pub const fn size() -> usize { 8 }
-
 
-    pub fn parse(slice_data: &[u8]) -> Result<Trim, &'static str> {
-        if slice_data.len() == Trim::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(Trim{
-                roll: ext.get_i16(),
-                pitch: ext.get_i16(),
-                yaw: ext.get_i16(),
-                throttle: ext.get_i16(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
+// -- Weight -----------------------------------------------------------------------------------------------
+#[derive(Debug, Copy, Clone, PartialEq, Default, Protocol)]
+pub struct Weight {
+    pub weight: f32,    // 무게(g)
 }
 
 
-impl Serializable for Trim {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.roll.to_le_bytes());
-        vec_data.extend_from_slice(&self.pitch.to_le_bytes());
-        vec_data.extend_from_slice(&self.yaw.to_le_bytes());
-        vec_data.extend_from_slice(&self.throttle.to_le_bytes());
-
-        vec_data
+impl Weight {
+    pub fn new() -> Weight{
+        Weight {
+            weight: 0.0_f32,
+        }
     }
 }
 
 
 
 // -- MagnetometerOffset -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Protocol)]
 pub struct MagnetometerOffset {
     pub offset: i16,
 }
@@ -592,31 +294,6 @@ impl MagnetometerOffset {
             offset: 0,
         }
     }
-
-
-    pub const fn size() -> usize { 2 }
-
-
-    pub fn parse(slice_data: &[u8]) -> Result<MagnetometerOffset, &'static str> {
-        if slice_data.len() == MagnetometerOffset::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
-            Ok(MagnetometerOffset{
-                offset: ext.get_i16(),
-            })
-        }
-        else { Err("Wrong length") }
-    }
-}
-
-
-impl Serializable for MagnetometerOffset {
-    fn to_vec(&self) -> Vec<u8> {
-        let mut vec_data : Vec<u8> = Vec::new();
-
-        vec_data.extend_from_slice(&self.offset.to_le_bytes());
-
-        vec_data
-    }
 }
 
 
@@ -2,8 +2,8 @@ use num_enum::IntoPrimitive;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
-use crate::protocol::Serializable;
-use crate::communication::extractor::Extractor;
+use crate::protocol::{ProtocolError, Serializable, enum_from_u8};
+use crate::communication::extractor::CheckedExtractor;
 
 
 // -- Mode ----------------------------------------------------------------------------------------------
@@ -30,7 +30,7 @@ impl Mode {
 
 
 // -- Vibrator -----------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Vibrator {
     pub mode: Mode,
     pub on: u16,
@@ -53,17 +53,17 @@ impl Vibrator {
     pub const fn size() -> usize { 7 }
 
 
-    pub fn parse(slice_data: &[u8]) -> Result<Vibrator, &'static str> {
+    pub fn parse(slice_data: &[u8]) -> Result<Vibrator, ProtocolError> {
         if slice_data.len() == Vibrator::size() {
-            let mut ext: Extractor = Extractor::from_slice(slice_data);
+            let mut ext: CheckedExtractor = CheckedExtractor::new(slice_data);
             Ok(Vibrator{
-                mode: Mode::from_u8(ext.get_u8()),
-                on: ext.get_u16(),
-                off: ext.get_u16(),
-                time: ext.get_u16(),
+                mode: enum_from_u8("Vibrator::mode", ext.get_u8()?)?,
+                on: ext.get_u16()?,
+                off: ext.get_u16()?,
+                time: ext.get_u16()?,
             })
         }
-        else { Err("Wrong length") }
+        else { Err(ProtocolError::LengthMismatch{ expected: Vibrator::size(), actual: slice_data.len() }) }
     }
 }
 
@@ -306,7 +306,7 @@ impl ModeUpdate {
 
 
 // -- Version -------------------------------------------------------------------------------------------
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Version {
     pub build: u16,
     pub minor: u8,